@@ -0,0 +1,36 @@
+//! Throughput of `Color::to_color_space` for the conversions renderers do most.
+//!
+//! ```sh
+//! cargo bench --bench conversion
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use csscolor::{Color, ColorSpace};
+
+fn conversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_color_space");
+
+    let pairs = [
+        (ColorSpace::Srgb, ColorSpace::Oklab),
+        (ColorSpace::Srgb, ColorSpace::Oklch),
+        (ColorSpace::Oklch, ColorSpace::Srgb),
+        (ColorSpace::Srgb, ColorSpace::Lab),
+        (ColorSpace::Lab, ColorSpace::Srgb),
+        (ColorSpace::Srgb, ColorSpace::DisplayP3),
+        (ColorSpace::Srgb, ColorSpace::Hsl),
+        // Goes through XYZ, for comparison with the sRGB to Oklab shortcut.
+        (ColorSpace::DisplayP3, ColorSpace::Oklab),
+    ];
+
+    for (from, to) in pairs {
+        let color = Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0).to_color_space(from);
+        group.bench_function(format!("{} to {}", from.css_name(), to.css_name()), |b| {
+            b.iter(|| black_box(&color).to_color_space(black_box(to)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, conversion);
+criterion_main!(benches);
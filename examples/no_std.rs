@@ -0,0 +1,21 @@
+//! Checks that the crate builds without `std`, using `libm` for float math:
+//!
+//! ```sh
+//! cargo build --example no_std --no-default-features --features libm
+//! ```
+
+#![no_std]
+
+use csscolor::{Color, ColorSpace};
+
+/// Parse a CSS color and convert it to 8-bit sRGB, or return `None` if the
+/// color is not valid.
+pub fn to_rgba8(css: &str) -> Option<[u8; 4]> {
+    let color = Color::parse(css).ok()?;
+    Some(
+        color
+            .map_to_gamut(ColorSpace::Srgb)
+            .to_color_space(ColorSpace::Srgb)
+            .to_rgba8(),
+    )
+}
@@ -0,0 +1,345 @@
+use crate::{model::wrap_hue, Color, ColorFlags, ColorSpace, Components};
+
+impl Color {
+    /// Convert to `color_space`, change the component at `index` and convert
+    /// back to the original color space. A missing component is treated as 0
+    /// before it is changed.
+    fn adjust_component(
+        &self,
+        color_space: ColorSpace,
+        index: usize,
+        adjust: impl FnOnce(f32) -> f32,
+    ) -> Color {
+        let mut color = self.to_color_space(color_space);
+        let value = color.resolved_components()[index];
+        color = color.with_component(index, adjust(value));
+        color.to_color_space(self.color_space)
+    }
+
+    /// Make the color lighter by adding `amount * 100` to its Lab lightness.
+    pub fn lighten(&self, amount: f32) -> Color {
+        self.adjust_component(ColorSpace::Lab, 0, |lightness| {
+            (lightness + amount * 100.0).clamp(0.0, 100.0)
+        })
+    }
+
+    /// Make the color darker by subtracting `amount * 100` from its Lab
+    /// lightness.
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+    /// Make the color more colorful by scaling its LCH chroma by
+    /// `1 + amount`. Achromatic colors are left as they are.
+    pub fn saturate(&self, amount: f32) -> Color {
+        self.adjust_component(ColorSpace::Lch, 1, |chroma| {
+            (chroma * (1.0 + amount)).max(0.0)
+        })
+    }
+
+    /// Make the color less colorful by scaling its LCH chroma by
+    /// `1 - amount`, so an amount of 1 results in a gray.
+    pub fn desaturate(&self, amount: f32) -> Color {
+        self.saturate(-amount)
+    }
+
+    /// Scale the LCH chroma of the color by `factor` and map the result into
+    /// the gamut of `dest` with [`Color::map_to_gamut`], so that the color
+    /// stays displayable however much the chroma is increased. The result is
+    /// in `dest`.
+    pub fn scale_chroma(&self, factor: f32, dest: ColorSpace) -> Color {
+        self.adjust_component(ColorSpace::Lch, 1, |chroma| (chroma * factor).max(0.0))
+            .map_to_gamut(dest)
+    }
+
+    /// Rotate the hue of the color by `degrees`. The rotation is done in LCH,
+    /// rather than HSL, so that the lightness of the color is preserved.
+    /// Achromatic colors, where the hue is powerless, are left as they are.
+    pub fn rotate_hue(&self, degrees: f32) -> Color {
+        let mut lch = self.to_color_space(ColorSpace::Lch);
+        let hue = lch.components.2;
+        if lch.flags.contains(ColorFlags::C2_IS_NONE) || hue.is_nan() {
+            return self.clone();
+        }
+
+        lch.components.2 = wrap_hue(hue + degrees);
+        lch.to_color_space(self.color_space)
+    }
+
+    /// The perceptual complement of the color, with its LCH hue rotated by 180
+    /// degrees.
+    pub fn complement(&self) -> Color {
+        self.rotate_hue(180.0)
+    }
+
+    /// A gray with the same LCH lightness as the color.
+    pub fn grayscale(&self) -> Color {
+        let lch = self
+            .to_color_space(ColorSpace::Lch)
+            .with_component(1, 0.0)
+            .set_none(2);
+        lch.to_color_space(self.color_space)
+    }
+
+    /// Mix white into the color by adding `amount` to its HWB whiteness. The
+    /// whiteness is clamped so that whiteness and blackness never add up to
+    /// more than 1.
+    pub fn add_whiteness(&self, amount: f32) -> Color {
+        self.adjust_hwb(1, amount)
+    }
+
+    /// Mix black into the color by adding `amount` to its HWB blackness. The
+    /// blackness is clamped so that whiteness and blackness never add up to
+    /// more than 1.
+    pub fn add_blackness(&self, amount: f32) -> Color {
+        self.adjust_hwb(2, amount)
+    }
+
+    fn adjust_hwb(&self, index: usize, amount: f32) -> Color {
+        let hwb = self.to_color_space(ColorSpace::Hwb);
+        let [_, whiteness, blackness] = hwb.resolved_components();
+        let (value, other) = if index == 1 {
+            (whiteness, blackness)
+        } else {
+            (blackness, whiteness)
+        };
+
+        let value = (value + amount).clamp(0.0, (1.0 - other).max(0.0));
+        hwb.with_component(index, value)
+            .to_color_space(self.color_space)
+    }
+
+    /// Invert the color in sRGB, like the CSS `invert()` filter. Missing
+    /// components are treated as 0. The result is in sRGB.
+    pub fn invert(&self) -> Color {
+        let srgb = self.to_color_space(ColorSpace::Srgb);
+        let [red, green, blue] = srgb.resolved_components().map(|value| 1.0 - value);
+
+        Color {
+            components: Components(red, green, blue),
+            flags: srgb.flags & ColorFlags::ALPHA_IS_NONE,
+            ..srgb
+        }
+    }
+
+    /// Compress the color into the displayable range with the Reinhard
+    /// operator, `c / (1 + c)`, applied to each linear-light sRGB channel.
+    /// Unlike clipping, bright values keep their relative order, and small
+    /// values are nearly unchanged. Negative channels are compressed
+    /// symmetrically. Missing components are treated as 0. The result is in
+    /// sRGB.
+    pub fn tone_map_reinhard(&self) -> Color {
+        let linear = self.to_linear_srgb();
+        let [red, green, blue] = linear
+            .resolved_components()
+            .map(|value| value / (1.0 + value.abs()));
+
+        Color {
+            components: Components(red, green, blue),
+            flags: linear.flags & ColorFlags::ALPHA_IS_NONE,
+            ..linear
+        }
+        .to_color_space(ColorSpace::Srgb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lighten_and_darken() {
+        let gray = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.5, 0.75);
+
+        let lighter = gray.lighten(0.1);
+        assert_eq!(lighter.color_space, ColorSpace::Srgb);
+        assert_eq!(lighter.alpha, 0.75);
+        assert!(lighter.relative_luminance() > gray.relative_luminance());
+
+        let darker = gray.darken(0.1);
+        assert!(darker.relative_luminance() < gray.relative_luminance());
+
+        let lab = gray.to_color_space(ColorSpace::Lab);
+        let lighter = lighter.to_color_space(ColorSpace::Lab);
+        assert!((lighter.components.0 - lab.components.0 - 10.0).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn saturate_and_desaturate() {
+        let color = Color::new(ColorSpace::Srgb, 0.6, 0.4, 0.3, 1.0);
+        let chroma = |color: &Color| color.to_color_space(ColorSpace::Lch).components.1;
+
+        let saturated = color.saturate(0.5);
+        assert_eq!(saturated.color_space, ColorSpace::Srgb);
+        assert!((chroma(&saturated) - chroma(&color) * 1.5).abs() < 1.0e-2);
+
+        let desaturated = color.desaturate(0.5);
+        assert!(chroma(&desaturated) < chroma(&color));
+
+        let gray = color.desaturate(1.0);
+        assert!((gray.components.0 - gray.components.1).abs() < 1.0e-4);
+        assert!((gray.components.1 - gray.components.2).abs() < 1.0e-4);
+        assert_eq!(color.desaturate(2.0), gray);
+    }
+
+    #[test]
+    fn scale_chroma_stays_in_gamut() {
+        let color = Color::new(ColorSpace::Srgb, 0.6, 0.4, 0.3, 1.0);
+        let chroma = |color: &Color| color.to_color_space(ColorSpace::Lch).components.1;
+
+        let vivid = color.scale_chroma(10.0, ColorSpace::Srgb);
+        assert_eq!(vivid.color_space, ColorSpace::Srgb);
+        assert!(vivid.is_in_gamut(ColorSpace::Srgb));
+        assert!(chroma(&vivid) > chroma(&color));
+
+        let muted = color.scale_chroma(0.5, ColorSpace::Srgb);
+        assert!((chroma(&muted) - chroma(&color) * 0.5).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn saturating_an_achromatic_color_does_nothing() {
+        let gray = Color::new(ColorSpace::Lch, 50.0, 0.0, None, 1.0);
+        assert_eq!(gray.saturate(0.5), gray);
+        assert_eq!(gray.desaturate(0.5), gray);
+    }
+
+    #[test]
+    fn rotate_hue() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5);
+        let rotated = red.rotate_hue(120.0);
+        assert_eq!(rotated.color_space, ColorSpace::Srgb);
+        assert_eq!(rotated.alpha, 0.5);
+
+        let before = red.to_color_space(ColorSpace::Lch);
+        let after = rotated.to_color_space(ColorSpace::Lch);
+        assert!((after.components.0 - before.components.0).abs() < 1.0e-2);
+        assert!((after.components.1 - before.components.1).abs() < 1.0e-2);
+        assert!((after.components.2 - (before.components.2 + 120.0)).abs() < 1.0e-2);
+
+        let lch = Color::new(ColorSpace::Lch, 50.0, 30.0, 300.0, 1.0);
+        assert_eq!(lch.rotate_hue(90.0).components.2, 30.0);
+        assert_eq!(lch.rotate_hue(-330.0).components.2, 330.0);
+    }
+
+    #[test]
+    fn rotating_an_achromatic_color_does_nothing() {
+        let gray = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.5, 1.0);
+        assert_eq!(gray.rotate_hue(90.0), gray);
+    }
+
+    #[test]
+    fn complement() {
+        let color = Color::new(ColorSpace::Srgb, 0.8, 0.4, 0.2, 0.75);
+        let complement = color.complement();
+        assert_eq!(complement.color_space, ColorSpace::Srgb);
+        assert_eq!(complement.alpha, 0.75);
+
+        let hue = |color: &Color| color.to_color_space(ColorSpace::Lch).components.2;
+        assert!(((hue(&complement) - hue(&color)).rem_euclid(360.0) - 180.0).abs() < 1.0e-2);
+
+        assert!(complement.complement().approx_eq(&color, 1.0e-4));
+    }
+
+    #[test]
+    fn grayscale() {
+        let color = Color::new(ColorSpace::Srgb, 0.8, 0.4, 0.2, 0.75);
+        let gray = color.grayscale();
+        assert_eq!(gray.color_space, ColorSpace::Srgb);
+        assert_eq!(gray.alpha, 0.75);
+
+        let before = color.to_color_space(ColorSpace::Lch);
+        let after = gray.to_color_space(ColorSpace::Lch);
+        assert!((after.components.0 - before.components.0).abs() < 1.0e-3);
+        assert!(after.components.1.abs() < 1.0e-3);
+
+        let lch = Color::new(ColorSpace::Lch, 50.0, 30.0, 120.0, 1.0).grayscale();
+        assert_eq!(lch, Color::new(ColorSpace::Lch, 50.0, 0.0, None, 1.0));
+    }
+
+    #[test]
+    fn invert() {
+        let white = Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 0.5);
+        assert_eq!(
+            white.invert(),
+            Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 0.5)
+        );
+
+        let color = Color::new(ColorSpace::Srgb, 0.75, 0.5, 0.25, 1.0);
+        assert_eq!(color.invert().invert(), color);
+
+        let missing = Color::new(ColorSpace::Srgb, None, 0.25, 1.0, None);
+        assert_eq!(
+            missing.invert(),
+            Color::new(ColorSpace::Srgb, 1.0, 0.75, 0.0, None)
+        );
+
+        let lab_white = Color::new(ColorSpace::Lab, 100.0, 0.0, 0.0, 1.0);
+        let black = lab_white.invert();
+        assert_eq!(black.color_space, ColorSpace::Srgb);
+        assert!(black.resolved_components().iter().all(|c| c.abs() < 1.0e-3));
+    }
+
+    #[test]
+    fn add_whiteness_and_blackness() {
+        let color = Color::new(ColorSpace::Srgb, 0.8, 0.4, 0.2, 0.5);
+        let white = Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 0.5);
+        let black = Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 0.5);
+
+        let whiter = color.add_whiteness(0.3);
+        assert_eq!(whiter.color_space, ColorSpace::Srgb);
+        assert_eq!(whiter.alpha, 0.5);
+        assert!(whiter.delta_e_ok(&white) < color.delta_e_ok(&white));
+        assert!(whiter.relative_luminance() > color.relative_luminance());
+
+        let blacker = color.add_blackness(0.3);
+        assert!(blacker.delta_e_ok(&black) < color.delta_e_ok(&black));
+
+        // Whiteness and blackness never add up to more than 1.
+        let hwb = Color::new(ColorSpace::Hwb, 30.0, 0.2, 0.5, 1.0);
+        assert_eq!(
+            hwb.add_whiteness(1.0),
+            Color::new(ColorSpace::Hwb, 30.0, 0.5, 0.5, 1.0)
+        );
+        assert_eq!(
+            hwb.add_blackness(1.0),
+            Color::new(ColorSpace::Hwb, 30.0, 0.2, 0.8, 1.0)
+        );
+        assert_eq!(
+            hwb.add_whiteness(-1.0),
+            Color::new(ColorSpace::Hwb, 30.0, 0.0, 0.5, 1.0)
+        );
+
+        // The blackness of the color is 0.2, so the most it can get is a gray.
+        let gray = Color::new(ColorSpace::Srgb, 0.8, 0.8, 0.8, 0.5);
+        assert!(color.add_whiteness(1.0).approx_eq(&gray, 1.0e-4));
+    }
+
+    #[test]
+    fn tone_map_reinhard() {
+        let bright = Color::new(ColorSpace::SrgbLinear, 4.0, 1.0, 0.5, 0.5);
+        let mapped = bright.tone_map_reinhard();
+        assert_eq!(mapped.color_space, ColorSpace::Srgb);
+        assert_eq!(mapped.alpha, 0.5);
+
+        let [red, green, blue] = mapped.to_linear_srgb().resolved_components();
+        assert!((red - 0.8).abs() < 1.0e-4);
+        assert!((green - 0.5).abs() < 1.0e-4);
+        assert!(blue < green && green < red && red < 1.0);
+
+        let dark = Color::new(ColorSpace::SrgbLinear, 0.01, 0.005, 0.0, 1.0);
+        let mapped = dark.tone_map_reinhard().to_linear_srgb();
+        assert!(mapped.approx_eq(&dark, 1.0e-4));
+    }
+
+    #[test]
+    fn lightness_is_clamped() {
+        let lab = Color::new(ColorSpace::Lab, 90.0, 10.0, -10.0, 1.0);
+        assert_eq!(lab.lighten(0.5).components.0, 100.0);
+        assert_eq!(lab.darken(1.5).components.0, 0.0);
+
+        let missing = Color::new(ColorSpace::Lab, None, 10.0, -10.0, 1.0);
+        let lighter = missing.lighten(0.25);
+        assert_eq!(lighter.components.0, 25.0);
+        assert_eq!(lighter.flags, ColorFlags::empty());
+    }
+}
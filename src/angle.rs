@@ -0,0 +1,77 @@
+use core::fmt;
+
+/// A CSS `<angle>`, used for hue components.
+/// <https://drafts.csswg.org/css-values-4/#angles>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Angle {
+    Deg(f32),
+    /// There are 400 gradians in a full circle.
+    Grad(f32),
+    Rad(f32),
+    /// There is 1 turn in a full circle.
+    Turn(f32),
+}
+
+impl Angle {
+    /// Create an angle from a value and a CSS unit, which is matched
+    /// case-insensitively. Returns `None` if the unit is not an angle unit.
+    pub fn from_unit(value: f32, unit: &str) -> Option<Self> {
+        Some(match unit.to_ascii_lowercase().as_str() {
+            "deg" => Self::Deg(value),
+            "grad" => Self::Grad(value),
+            "rad" => Self::Rad(value),
+            "turn" => Self::Turn(value),
+            _ => return None,
+        })
+    }
+
+    /// The angle in degrees. The result is not normalized to `[0, 360)`.
+    pub fn to_degrees(self) -> f32 {
+        match self {
+            Self::Deg(value) => value,
+            Self::Grad(value) => value * 0.9,
+            Self::Rad(value) => value.to_degrees(),
+            Self::Turn(value) => value * 360.0,
+        }
+    }
+}
+
+impl fmt::Display for Angle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deg(value) => write!(f, "{value}deg"),
+            Self::Grad(value) => write!(f, "{value}grad"),
+            Self::Rad(value) => write!(f, "{value}rad"),
+            Self::Turn(value) => write!(f, "{value}turn"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_degrees() {
+        for angle in [
+            Angle::Deg(180.0),
+            Angle::Grad(200.0),
+            Angle::Rad(std::f32::consts::PI),
+            Angle::Turn(0.5),
+        ] {
+            assert!(
+                (angle.to_degrees() - 180.0).abs() < 1.0e-4,
+                "{angle} is not 180 degrees"
+            );
+        }
+    }
+
+    #[test]
+    fn from_unit() {
+        assert_eq!(Angle::from_unit(1.0, "deg"), Some(Angle::Deg(1.0)));
+        assert_eq!(Angle::from_unit(1.0, "GRAD"), Some(Angle::Grad(1.0)));
+        assert_eq!(Angle::from_unit(1.0, "Rad"), Some(Angle::Rad(1.0)));
+        assert_eq!(Angle::from_unit(1.0, "turn"), Some(Angle::Turn(1.0)));
+        assert_eq!(Angle::from_unit(1.0, "px"), None);
+    }
+}
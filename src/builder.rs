@@ -0,0 +1,106 @@
+use crate::{Color, ColorFlags, ColorSpace};
+
+/// Build a [`Color`] one component at a time, which is easier to read than
+/// passing a mix of `f32` and `Option<f32>` to [`Color::new`] when only some of
+/// the components are missing. Components start at 0 and alpha starts at 1.
+#[derive(Clone, Debug)]
+pub struct ColorBuilder {
+    color: Color,
+}
+
+impl Default for ColorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColorBuilder {
+    pub fn new() -> Self {
+        Self {
+            color: Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 1.0),
+        }
+    }
+
+    /// Set the component at `index` to `value`. Panics if `index` is out of
+    /// bounds.
+    pub fn component(mut self, index: usize, value: f32) -> Self {
+        self.color = self.color.with_component(index, value);
+        self
+    }
+
+    /// Mark the component at `index` as missing. Panics if `index` is out of
+    /// bounds.
+    pub fn none(mut self, index: usize) -> Self {
+        self.color = self.color.set_none(index);
+        self
+    }
+
+    pub fn alpha(mut self, alpha: f32) -> Self {
+        self.color = self.color.with_alpha(alpha);
+        self
+    }
+
+    /// Mark the alpha as missing.
+    pub fn alpha_none(mut self) -> Self {
+        self.color.alpha = 0.0;
+        self.color.flags.insert(ColorFlags::ALPHA_IS_NONE);
+        self
+    }
+
+    /// Create the color, interpreting the components in `color_space`.
+    pub fn build(&self, color_space: ColorSpace) -> Color {
+        Color {
+            color_space,
+            ..self.color.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_with_missing_component() {
+        let color = ColorBuilder::new()
+            .component(0, 50.0)
+            .none(1)
+            .component(2, 120.0)
+            .alpha(0.5)
+            .build(ColorSpace::Lch);
+
+        assert_eq!(color, Color::new(ColorSpace::Lch, 50.0, None, 120.0, 0.5));
+        assert_eq!(color.flags, ColorFlags::C1_IS_NONE);
+    }
+
+    #[test]
+    fn later_calls_override_earlier_ones() {
+        let color = ColorBuilder::new()
+            .none(0)
+            .component(0, 0.25)
+            .alpha_none()
+            .build(ColorSpace::Srgb);
+        assert_eq!(color, Color::new(ColorSpace::Srgb, 0.25, 0.0, 0.0, None));
+
+        let color = ColorBuilder::new()
+            .alpha_none()
+            .alpha(0.75)
+            .build(ColorSpace::Srgb);
+        assert_eq!(color, Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 0.75));
+    }
+
+    #[test]
+    fn defaults_to_opaque_zero_components() {
+        let builder = ColorBuilder::default();
+        assert_eq!(
+            builder.build(ColorSpace::Oklab),
+            Color::new(ColorSpace::Oklab, 0.0, 0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds_component_panics() {
+        let _ = ColorBuilder::new().none(3);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{Color, ColorSpace};
+use core::num::NonZeroUsize;
+use lru::LruCache;
+
+/// Remembers the results of recent conversions, for callers that convert the
+/// same colors over and over, like a UI that recolors a few base colors every
+/// frame. Colors are looked up by the same canonical bits that `==` compares,
+/// so `-0.0` and `0.0`, and all NaNs, are the same key. When the cache is full
+/// the least recently used conversion is dropped.
+pub struct ConversionCache {
+    conversions: LruCache<(Color, ColorSpace), Color>,
+}
+
+impl Default for ConversionCache {
+    /// A cache for the 256 most recent conversions.
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl ConversionCache {
+    /// Create a cache that keeps the `capacity` most recent conversions. A
+    /// `capacity` of 0 is treated as 1.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            conversions: LruCache::new(NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN)),
+        }
+    }
+
+    /// Convert `color` to `color_space`, like [`Color::to_color_space`], but
+    /// return the remembered result if the same conversion was done recently.
+    pub fn convert(&mut self, color: &Color, color_space: ColorSpace) -> Color {
+        self.conversions
+            .get_or_insert((color.clone(), color_space), || {
+                color.to_color_space(color_space)
+            })
+            .clone()
+    }
+
+    /// The number of conversions that are remembered.
+    pub fn len(&self) -> usize {
+        self.conversions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conversions.is_empty()
+    }
+
+    /// Forget all the remembered conversions.
+    pub fn clear(&mut self) {
+        self.conversions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_hit_matches_direct_conversion() {
+        let mut cache = ConversionCache::default();
+        let color = Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 0.5);
+        let direct = color.to_color_space(ColorSpace::Oklch);
+
+        assert_eq!(cache.convert(&color, ColorSpace::Oklch), direct);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.convert(&color, ColorSpace::Oklch), direct);
+        assert_eq!(cache.len(), 1);
+
+        // The same color with -0 instead of 0 is the same key.
+        let zero = Color::new(ColorSpace::Srgb, 0.0, 0.5, 1.0, 1.0);
+        let negative_zero = Color::new(ColorSpace::Srgb, -0.0, 0.5, 1.0, 1.0);
+        cache.convert(&zero, ColorSpace::Lab);
+        cache.convert(&negative_zero, ColorSpace::Lab);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn least_recently_used_conversion_is_dropped() {
+        let mut cache = ConversionCache::new(2);
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let green = Color::new(ColorSpace::Srgb, 0.0, 1.0, 0.0, 1.0);
+
+        cache.convert(&red, ColorSpace::Lab);
+        cache.convert(&green, ColorSpace::Lab);
+        cache.convert(&red, ColorSpace::Lab);
+        cache.convert(&red, ColorSpace::Oklab);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.conversions.contains(&(red.clone(), ColorSpace::Lab)));
+        assert!(!cache.conversions.contains(&(green, ColorSpace::Lab)));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(ConversionCache::new(0).conversions.cap().get(), 1);
+    }
+}
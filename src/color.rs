@@ -1,7 +1,12 @@
-use crate::model::ColorSpaceModel;
+use crate::model::{wrap_hue, ColorSpaceModel};
 use bitflags::bitflags;
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Index, IndexMut},
+};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum ColorSpace {
     Srgb,
     Hsl,
@@ -20,6 +25,26 @@ pub enum ColorSpace {
 }
 
 impl ColorSpace {
+    /// Every color space, in declaration order.
+    pub fn all() -> &'static [ColorSpace] {
+        &[
+            Self::Srgb,
+            Self::Hsl,
+            Self::Hwb,
+            Self::Lab,
+            Self::Lch,
+            Self::Oklab,
+            Self::Oklch,
+            Self::SrgbLinear,
+            Self::DisplayP3,
+            Self::A98Rgb,
+            Self::ProphotoRgb,
+            Self::Rec2020,
+            Self::XyzD50,
+            Self::XyzD65,
+        ]
+    }
+
     pub fn is_rgb_like(&self) -> bool {
         matches!(
             self,
@@ -38,10 +63,123 @@ impl ColorSpace {
     pub fn is_xyz_like(&self) -> bool {
         matches!(self, Self::XyzD50 | Self::XyzD65)
     }
+
+    /// What each of the three components of the color space represents.
+    pub fn component_kinds(&self) -> [ComponentKind; 3] {
+        use ComponentKind as K;
+
+        match self {
+            Self::Srgb
+            | Self::SrgbLinear
+            | Self::DisplayP3
+            | Self::A98Rgb
+            | Self::ProphotoRgb
+            | Self::Rec2020 => [K::Red, K::Green, K::Blue],
+            Self::Hsl => [K::Hue, K::Saturation, K::Lightness],
+            Self::Hwb => [K::Hue, K::Whiteness, K::Blackness],
+            Self::Lab | Self::Oklab => [K::Lightness, K::OpponentA, K::OpponentB],
+            Self::Lch | Self::Oklch => [K::Lightness, K::Chroma, K::Hue],
+            Self::XyzD50 | Self::XyzD65 => [K::X, K::Y, K::Z],
+        }
+    }
+
+    /// The index of the component that holds the hue angle, or `None` if the
+    /// color space has no hue.
+    pub fn hue_index(&self) -> Option<usize> {
+        self.component_kinds()
+            .iter()
+            .position(|kind| *kind == ComponentKind::Hue)
+    }
+
+    /// The CSS identifier for the color space.
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            Self::Srgb => "srgb",
+            Self::Hsl => "hsl",
+            Self::Hwb => "hwb",
+            Self::Lab => "lab",
+            Self::Lch => "lch",
+            Self::Oklab => "oklab",
+            Self::Oklch => "oklch",
+            Self::SrgbLinear => "srgb-linear",
+            Self::DisplayP3 => "display-p3",
+            Self::A98Rgb => "a98-rgb",
+            Self::ProphotoRgb => "prophoto-rgb",
+            Self::Rec2020 => "rec2020",
+            Self::XyzD50 => "xyz-d50",
+            Self::XyzD65 => "xyz-d65",
+        }
+    }
+
+    /// The color space for a CSS identifier, matched case-insensitively.
+    pub fn from_css_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "srgb" => Self::Srgb,
+            "hsl" => Self::Hsl,
+            "hwb" => Self::Hwb,
+            "lab" => Self::Lab,
+            "lch" => Self::Lch,
+            "oklab" => Self::Oklab,
+            "oklch" => Self::Oklch,
+            "srgb-linear" => Self::SrgbLinear,
+            "display-p3" => Self::DisplayP3,
+            "a98-rgb" => Self::A98Rgb,
+            "prophoto-rgb" => Self::ProphotoRgb,
+            "rec2020" => Self::Rec2020,
+            "xyz-d50" => Self::XyzD50,
+            "xyz-d65" => Self::XyzD65,
+            _ => return None,
+        })
+    }
+}
+
+/// What a component of a color space represents.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ComponentKind {
+    Red,
+    Green,
+    Blue,
+    X,
+    Y,
+    Z,
+    Hue,
+    Saturation,
+    Lightness,
+    Whiteness,
+    Blackness,
+    Chroma,
+    OpponentA,
+    OpponentB,
+}
+
+impl ComponentKind {
+    /// Whether the two kinds are analogous, so a missing component of one kind
+    /// carries over to a component of the other kind when converting. Red is
+    /// analogous to X, green to Y, blue to Z and saturation to chroma.
+    /// Whiteness and blackness have no analogous components.
+    /// <https://drafts.csswg.org/css-color-4/#interpolation-missing>
+    pub fn is_analogous_to(&self, other: ComponentKind) -> bool {
+        use ComponentKind as K;
+
+        let group = |kind: ComponentKind| match kind {
+            K::Red | K::X => Some(0),
+            K::Green | K::Y => Some(1),
+            K::Blue | K::Z => Some(2),
+            K::Lightness => Some(3),
+            K::Saturation | K::Chroma => Some(4),
+            K::Hue => Some(5),
+            K::OpponentA => Some(6),
+            K::OpponentB => Some(7),
+            K::Whiteness | K::Blackness => None,
+        };
+
+        group(*self).is_some() && group(*self) == group(other)
+    }
 }
 
 bitflags! {
-    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ColorFlags : u8 {
         const C0_IS_NONE = 1 << 0;
         const C1_IS_NONE = 1 << 1;
@@ -50,7 +188,6 @@ bitflags! {
     }
 }
 
-// pub type Components = [f32; 3];
 #[derive(Clone, Debug, PartialEq)]
 #[repr(C)]
 pub struct Components(pub f32, pub f32, pub f32);
@@ -61,7 +198,43 @@ impl Components {
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+impl From<[f32; 3]> for Components {
+    fn from([c0, c1, c2]: [f32; 3]) -> Self {
+        Self(c0, c1, c2)
+    }
+}
+
+impl From<Components> for [f32; 3] {
+    fn from(Components(c0, c1, c2): Components) -> Self {
+        [c0, c1, c2]
+    }
+}
+
+impl Index<usize> for Components {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.0,
+            1 => &self.1,
+            2 => &self.2,
+            _ => panic!("Component index out of bounds ({index})"),
+        }
+    }
+}
+
+impl IndexMut<usize> for Components {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.0,
+            1 => &mut self.1,
+            2 => &mut self.2,
+            _ => panic!("Component index out of bounds ({index})"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 #[repr(C)]
 pub struct Color {
     pub components: Components,
@@ -70,6 +243,93 @@ pub struct Color {
     pub alpha: f32,
 }
 
+/// The stored value of a component, ignoring whether it is missing. Panics if
+/// `index` is out of bounds.
+impl Index<usize> for Color {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.components[index]
+    }
+}
+
+impl IndexMut<usize> for Color {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.components[index]
+    }
+}
+
+/// Transparent black in sRGB.
+impl Default for Color {
+    fn default() -> Self {
+        Self::transparent()
+    }
+}
+
+/// The bits of a component, with all NaNs mapped to a single value and -0
+/// mapped to 0.
+fn canonical_bits(value: f32) -> u32 {
+    if value.is_nan() {
+        f32::NAN.to_bits()
+    } else {
+        // Adding 0 turns -0 into 0.
+        (value + 0.0).to_bits()
+    }
+}
+
+impl Color {
+    fn canonical_bits(&self) -> [u32; 4] {
+        let Components(c0, c1, c2) = self.components;
+        [c0, c1, c2, self.alpha].map(canonical_bits)
+    }
+}
+
+/// Colors are equal if they are in the same color space, have the same flags
+/// and their components have the same value. Unlike plain float comparison,
+/// NaN components, like the hue of an achromatic color, are equal to each
+/// other, which makes it possible to use colors as keys in maps.
+impl PartialEq for Color {
+    fn eq(&self, other: &Self) -> bool {
+        self.color_space == other.color_space
+            && self.flags == other.flags
+            && self.canonical_bits() == other.canonical_bits()
+    }
+}
+
+impl Eq for Color {}
+
+/// Hashes the same canonical bits that are used for equality. This only agrees
+/// with [`Color::approx_eq`] when the colors are exactly equal.
+impl Hash for Color {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_bits().hash(state);
+        self.flags.hash(state);
+        self.color_space.hash(state);
+    }
+}
+
+/// The error returned when a color is accessed as the model of a different
+/// color space.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ColorSpaceMismatch {
+    /// The color space of the requested model.
+    pub expected: ColorSpace,
+    /// The color space of the color.
+    pub actual: ColorSpace,
+}
+
+impl fmt::Display for ColorSpaceMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "color is in {:?}, not the requested color space ({:?})",
+            self.actual, self.expected
+        )
+    }
+}
+
+impl core::error::Error for ColorSpaceMismatch {}
+
 /// Implement a From<*> for this struct to allow components of that type to be
 /// used to construct a new color.
 pub struct ComponentDetails {
@@ -144,14 +404,344 @@ impl Color {
         }
     }
 
-    pub fn as_model<C: ColorSpaceModel>(&self) -> &C {
+    /// The stored components and alpha as `[c0, c1, c2, alpha]`. Flags are
+    /// ignored, so missing components are returned as their stored value.
+    pub fn to_array(&self) -> [f32; 4] {
+        let Components(c0, c1, c2) = self.components;
+        [c0, c1, c2, self.alpha]
+    }
+
+    /// Create a color from `[c0, c1, c2, alpha]`, with all components present.
+    pub fn from_array(color_space: ColorSpace, [c0, c1, c2, alpha]: [f32; 4]) -> Self {
+        Self {
+            components: Components(c0, c1, c2),
+            flags: ColorFlags::empty(),
+            color_space,
+            alpha,
+        }
+    }
+
+    /// Create a color from its parts. Unlike [`Color::new`], this is a
+    /// `const fn`, so it can be used to define constants. Missing components
+    /// are marked with `flags` only, so their values should be 0.
+    pub const fn from_components(
+        color_space: ColorSpace,
+        [c0, c1, c2]: [f32; 3],
+        alpha: f32,
+        flags: ColorFlags,
+    ) -> Self {
+        Self {
+            components: Components(c0, c1, c2),
+            flags,
+            color_space,
+            alpha,
+        }
+    }
+
+    /// Fully transparent black, which is what the CSS `transparent` keyword
+    /// resolves to.
+    pub fn transparent() -> Self {
+        Self::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 0.0)
+    }
+
+    /// The components of the color, with missing components, including NaN
+    /// hues, replaced by 0.
+    pub fn resolved_components(&self) -> [f32; 3] {
+        let Components(c0, c1, c2) = self.components;
+
+        [
+            (c0, ColorFlags::C0_IS_NONE),
+            (c1, ColorFlags::C1_IS_NONE),
+            (c2, ColorFlags::C2_IS_NONE),
+        ]
+        .map(|(value, flag)| {
+            if self.flags.contains(flag) || value.is_nan() {
+                0.0
+            } else {
+                value
+            }
+        })
+    }
+
+    /// The alpha of the color, or 0 if it is missing.
+    pub fn resolved_alpha(&self) -> f32 {
+        if self.flags.contains(ColorFlags::ALPHA_IS_NONE) {
+            0.0
+        } else {
+            self.alpha
+        }
+    }
+
+    /// Whether any of the components or the alpha is missing.
+    pub fn has_missing(&self) -> bool {
+        !self.flags.is_empty()
+    }
+
+    /// Whether the component at `index` is missing, where an `index` of 3 is
+    /// the alpha. Panics if `index` is out of bounds.
+    pub fn is_none(&self, index: usize) -> bool {
+        let flag = match index {
+            3 => ColorFlags::ALPHA_IS_NONE,
+            index => Self::component_flag(index),
+        };
+        self.flags.contains(flag)
+    }
+
+    /// Which of the components and the alpha are missing, in the order
+    /// `[c0, c1, c2, alpha]`.
+    pub fn none_mask(&self) -> [bool; 4] {
+        [0, 1, 2, 3].map(|index| self.is_none(index))
+    }
+
+    /// Replace missing components and alpha with 0 and clear their flags, so
+    /// the color is fully specified.
+    pub fn resolve_missing(mut self) -> Self {
+        let [c0, c1, c2] = self.resolved_components();
+        self.components = Components(c0, c1, c2);
+        self.alpha = self.resolved_alpha();
+        self.flags = ColorFlags::empty();
+        self
+    }
+
+    /// Whether the two colors are in the same color space and each component
+    /// differs by no more than `epsilon`. Components that are missing, or NaN,
+    /// in both colors are considered equal.
+    pub fn approx_eq(&self, other: &Color, epsilon: f32) -> bool {
+        if self.color_space != other.color_space {
+            return false;
+        }
+
+        let values = |color: &Color| {
+            let Components(c0, c1, c2) = color.components;
+            [
+                (c0, ColorFlags::C0_IS_NONE),
+                (c1, ColorFlags::C1_IS_NONE),
+                (c2, ColorFlags::C2_IS_NONE),
+                (color.alpha, ColorFlags::ALPHA_IS_NONE),
+            ]
+            .map(|(value, flag)| (!color.flags.contains(flag) && !value.is_nan()).then_some(value))
+        };
+
+        values(self)
+            .into_iter()
+            .zip(values(other))
+            .all(|pair| match pair {
+                (None, None) => true,
+                (Some(a), Some(b)) => (a - b).abs() <= epsilon,
+                _ => false,
+            })
+    }
+
+    /// Whether the color is effectively a gray, within `epsilon`. RGB colors
+    /// are achromatic when their channels are equal, polar and HSL colors when
+    /// their chroma or saturation is 0 and HWB colors when whiteness and
+    /// blackness add up to at least 1. XYZ colors are checked in Oklab.
+    /// Missing components are treated as 0.
+    pub fn is_achromatic(&self, epsilon: f32) -> bool {
+        let [c0, c1, c2] = self.resolved_components();
+        match self.color_space {
+            ColorSpace::Srgb
+            | ColorSpace::SrgbLinear
+            | ColorSpace::DisplayP3
+            | ColorSpace::A98Rgb
+            | ColorSpace::ProphotoRgb
+            | ColorSpace::Rec2020 => {
+                (c0 - c1).abs() <= epsilon
+                    && (c1 - c2).abs() <= epsilon
+                    && (c0 - c2).abs() <= epsilon
+            }
+            ColorSpace::Hsl => c1 <= epsilon,
+            ColorSpace::Hwb => c1 + c2 >= 1.0 - epsilon,
+            ColorSpace::Lab | ColorSpace::Oklab => c1.abs() <= epsilon && c2.abs() <= epsilon,
+            ColorSpace::Lch | ColorSpace::Oklch => c1 <= epsilon,
+            ColorSpace::XyzD50 | ColorSpace::XyzD65 => {
+                let [_, a, b] = self.resolved_components_in(ColorSpace::Oklab);
+                a.abs() <= epsilon && b.abs() <= epsilon
+            }
+        }
+    }
+
+    /// The stored values of the three components, in order. Missing
+    /// components are yielded as their stored value.
+    pub fn components_iter(&self) -> impl Iterator<Item = f32> {
+        let Components(c0, c1, c2) = self.components;
+        [c0, c1, c2].into_iter()
+    }
+
+    /// Apply `f` to each of the three components, along with its index. The
+    /// color space, flags and alpha are kept, so missing components stay
+    /// missing.
+    pub fn map(&self, f: impl Fn(usize, f32) -> f32) -> Color {
+        let Components(c0, c1, c2) = self.components;
+        Color {
+            components: Components(f(0, c0), f(1, c1), f(2, c2)),
+            ..self.clone()
+        }
+    }
+
+    /// Bring the components into the canonical ranges of the color space:
+    /// hues are wrapped into `[0, 360)`, HSL and HWB percentages and alpha are
+    /// clamped to `[0, 1]`, whiteness and blackness are scaled down if they add
+    /// up to more than 1, lightness is clamped to its range and chroma is
+    /// clamped below at 0 but is unbounded above. Lab and Oklab a and b, and
+    /// RGB and XYZ channels are unbounded, so they are left as they are.
+    pub fn normalize(&self) -> Color {
+        let mut result = self.clone();
+        let c = &mut result.components;
+
+        if let Some(i) = self.color_space.hue_index() {
+            c[i] = wrap_hue(c[i]);
+        }
+
+        match self.color_space {
+            ColorSpace::Hsl => {
+                c.1 = c.1.clamp(0.0, 1.0);
+                c.2 = c.2.clamp(0.0, 1.0);
+            }
+            ColorSpace::Hwb => {
+                c.1 = c.1.clamp(0.0, 1.0);
+                c.2 = c.2.clamp(0.0, 1.0);
+                let sum = c.1 + c.2;
+                if sum > 1.0 {
+                    c.1 /= sum;
+                    c.2 /= sum;
+                }
+            }
+            ColorSpace::Lab => c.0 = c.0.clamp(0.0, 100.0),
+            ColorSpace::Lch => {
+                c.0 = c.0.clamp(0.0, 100.0);
+                c.1 = c.1.max(0.0);
+            }
+            ColorSpace::Oklab => c.0 = c.0.clamp(0.0, 1.0),
+            ColorSpace::Oklch => {
+                c.0 = c.0.clamp(0.0, 1.0);
+                c.1 = c.1.max(0.0);
+            }
+            ColorSpace::Srgb
+            | ColorSpace::SrgbLinear
+            | ColorSpace::DisplayP3
+            | ColorSpace::A98Rgb
+            | ColorSpace::ProphotoRgb
+            | ColorSpace::Rec2020
+            | ColorSpace::XyzD50
+            | ColorSpace::XyzD65 => {}
+        }
+        result.alpha = result.alpha.clamp(0.0, 1.0);
+
+        result
+    }
+
+    /// Clamp the components to the range that is valid for the color space:
+    /// RGB channels, HSL and HWB percentages and alpha to `[0, 1]`, and
+    /// lightness and chroma to at least 0. Unlike [`Color::normalize`], this
+    /// clamps RGB channels, so the result is in the gamut of RGB color spaces,
+    /// and it does not wrap hues. Lab and Oklab a and b and XYZ channels are
+    /// unbounded, so they are left as they are. Missing components are not
+    /// changed.
+    pub fn clamp(&self) -> Color {
+        let mut result = self.clone();
+        let c = &mut result.components;
+
+        match self.color_space {
+            ColorSpace::Srgb
+            | ColorSpace::SrgbLinear
+            | ColorSpace::DisplayP3
+            | ColorSpace::A98Rgb
+            | ColorSpace::ProphotoRgb
+            | ColorSpace::Rec2020 => *c = c.map(|value| value.clamp(0.0, 1.0)),
+            ColorSpace::Hsl | ColorSpace::Hwb => {
+                c.1 = c.1.clamp(0.0, 1.0);
+                c.2 = c.2.clamp(0.0, 1.0);
+            }
+            ColorSpace::Lab | ColorSpace::Oklab => c.0 = c.0.max(0.0),
+            ColorSpace::Lch | ColorSpace::Oklch => {
+                c.0 = c.0.max(0.0);
+                c.1 = c.1.max(0.0);
+            }
+            ColorSpace::XyzD50 | ColorSpace::XyzD65 => {}
+        }
+        result.alpha = result.alpha.clamp(0.0, 1.0);
+
+        result
+    }
+
+    /// Return the color with the given alpha, which is no longer missing.
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self.flags.remove(ColorFlags::ALPHA_IS_NONE);
+        self
+    }
+
+    /// Return the color with its alpha clamped to `[0, 1]`, as CSS does for
+    /// specified alpha values. [`Color::new`] stores the alpha as it is given.
+    /// A missing alpha stays missing.
+    pub fn clamp_alpha(mut self) -> Self {
+        self.alpha = self.alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Return the color with the component at `index` set to `value`, which is
+    /// no longer missing. Panics if `index` is out of bounds.
+    pub fn with_component(mut self, index: usize, value: f32) -> Self {
+        self.components[index] = value;
+        self.flags.remove(Self::component_flag(index));
+        self
+    }
+
+    /// Return the color with the component at `index` marked as missing.
+    /// Panics if `index` is out of bounds.
+    pub fn set_none(mut self, index: usize) -> Self {
+        self.components[index] = 0.0;
+        self.flags.insert(Self::component_flag(index));
+        self
+    }
+
+    /// Return the color with its components, flags and alpha unchanged, but
+    /// interpreted in `color_space`. No conversion is done, so this changes
+    /// what the color looks like: sRGB `1 0 0` reinterpreted as Lab is a
+    /// near-black, not red. Use [`Color::to_color_space`] to keep the color
+    /// the same. This is only useful for raw components that are known to be
+    /// in `color_space` but were labelled with another one.
+    pub fn reinterpret(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    fn component_flag(index: usize) -> ColorFlags {
+        match index {
+            0 => ColorFlags::C0_IS_NONE,
+            1 => ColorFlags::C1_IS_NONE,
+            2 => ColorFlags::C2_IS_NONE,
+            _ => panic!("Component index out of bounds ({index})"),
+        }
+    }
+
+    /// A copy of the color as the model of its color space, or `None` if the
+    /// color is in a different color space than `C`. Use [`Color::as_model`]
+    /// or [`Color::try_as_model`] to borrow the color instead.
+    pub fn to_model<C: ColorSpaceModel>(&self) -> Option<C> {
+        (self.color_space == C::COLOR_SPACE)
+            .then(|| C::from_components(self.components.clone(), self.flags))
+    }
+
+    /// Borrow the color as the model of its color space, or return an error
+    /// if the color is in a different color space than `C`.
+    pub fn try_as_model<C: ColorSpaceModel>(&self) -> Result<&C, ColorSpaceMismatch> {
         if self.color_space != C::COLOR_SPACE {
-            panic!(
-                "Color is not in the requested color space ({:?})",
-                C::COLOR_SPACE
-            );
+            return Err(ColorSpaceMismatch {
+                expected: C::COLOR_SPACE,
+                actual: self.color_space,
+            });
         }
-        unsafe { std::mem::transmute(self) }
+        // SAFETY: The models are sealed, and the layout of each one is checked
+        // at compile time to match the start of a `Color`.
+        Ok(unsafe { &*(self as *const Color).cast::<C>() })
+    }
+
+    /// Borrow the color as the model of its color space. Panics if the color
+    /// is in a different color space than `C`.
+    pub fn as_model<C: ColorSpaceModel>(&self) -> &C {
+        self.try_as_model().unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
@@ -185,4 +775,438 @@ mod tests {
                 | ColorFlags::ALPHA_IS_NONE
         );
     }
+
+    #[test]
+    fn as_model_exposes_oklab_and_oklch() {
+        use crate::{Oklab, Oklch};
+
+        let oklab = Color::new(ColorSpace::Oklab, 0.628, 0.225, 0.126, 1.0);
+        let model = oklab.as_model::<Oklab>();
+        assert_eq!(model.lightness, 0.628);
+        assert_eq!(model.a, 0.225);
+        assert_eq!(model.b, 0.126);
+
+        let oklch = Color::new(ColorSpace::Oklch, 0.628, 0.258, 29.2, 1.0);
+        let model = oklch.as_model::<Oklch>();
+        assert_eq!(model.lightness, 0.628);
+        assert_eq!(model.chroma, 0.258);
+        assert_eq!(model.hue, 29.2);
+    }
+
+    #[test]
+    fn try_as_model_reports_the_mismatch() {
+        use crate::{Lab, Srgb};
+
+        let color = Color::new(ColorSpace::Lab, 50.0, 20.0, -30.0, 1.0);
+        let err = color.try_as_model::<Srgb>().err().unwrap();
+        assert_eq!(
+            err,
+            ColorSpaceMismatch {
+                expected: ColorSpace::Srgb,
+                actual: ColorSpace::Lab,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "color is in Lab, not the requested color space (Srgb)"
+        );
+
+        assert!(color.try_as_model::<Lab>().is_ok());
+    }
+
+    #[test]
+    fn to_model_does_not_panic() {
+        use crate::{Oklab, Oklch};
+
+        let oklab = Color::new(ColorSpace::Oklab, 0.628, 0.225, 0.126, 1.0);
+        let model = oklab.to_model::<Oklab>().unwrap();
+        assert_eq!((model.lightness, model.a, model.b), (0.628, 0.225, 0.126));
+
+        assert!(oklab.to_model::<Oklch>().is_none());
+        let oklch = oklab.to_color_space(ColorSpace::Oklch);
+        assert!(oklch.to_model::<Oklch>().is_some());
+    }
+
+    #[test]
+    fn components_can_be_indexed() {
+        let mut components = Components::from([0.1, 0.2, 0.3]);
+        assert_eq!(components[0], 0.1);
+        assert_eq!(components[1], 0.2);
+        assert_eq!(components[2], 0.3);
+
+        components[1] = 0.5;
+        assert_eq!(components, Components(0.1, 0.5, 0.3));
+        assert_eq!(<[f32; 3]>::from(components), [0.1, 0.5, 0.3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn components_index_out_of_bounds() {
+        let _ = Components(0.1, 0.2, 0.3)[3];
+    }
+
+    #[test]
+    fn resolved_components_replace_none_with_zero() {
+        let mut color = Color::new(ColorSpace::Hsl, 120.0, 0.5, 0.25, None);
+        color.components.0 = 30.0;
+        color.flags |= ColorFlags::C0_IS_NONE;
+        assert_eq!(color.resolved_components(), [0.0, 0.5, 0.25]);
+        assert_eq!(color.resolved_alpha(), 0.0);
+
+        let lch = Color::new(ColorSpace::Lch, 50.0, 0.0, f32::NAN, 0.5);
+        assert_eq!(lch.resolved_components(), [50.0, 0.0, 0.0]);
+        assert_eq!(lch.resolved_alpha(), 0.5);
+    }
+
+    #[test]
+    fn approx_eq() {
+        let color = Color::new(ColorSpace::Srgb, 0.5, 0.25, 0.75, 1.0);
+        let close = Color::new(ColorSpace::Srgb, 0.50001, 0.24999, 0.75, 1.0);
+        assert!(color.approx_eq(&close, 1.0e-4));
+        assert!(!color.approx_eq(&close, 1.0e-6));
+
+        let other_space = Color::new(ColorSpace::DisplayP3, 0.5, 0.25, 0.75, 1.0);
+        assert!(!color.approx_eq(&other_space, 1.0e-4));
+
+        let missing = Color::new(ColorSpace::Srgb, 0.5, None, 0.75, 1.0);
+        assert!(!color.approx_eq(&missing, 1.0e-4));
+        assert!(missing.approx_eq(&missing, 0.0));
+
+        let none_hue = Color::new(ColorSpace::Lch, 50.0, 0.0, None, 1.0);
+        let nan_hue = Color::new(ColorSpace::Lch, 50.0, 0.0, f32::NAN, 1.0);
+        assert!(nan_hue.approx_eq(&nan_hue, 0.0));
+        assert!(none_hue.approx_eq(&nan_hue, 0.0));
+    }
+
+    #[test]
+    fn array_round_trip() {
+        let color = Color::new(ColorSpace::Oklch, 0.7, 0.15, 30.0, 0.5);
+        assert_eq!(color.to_array(), [0.7, 0.15, 30.0, 0.5]);
+        assert_eq!(
+            Color::from_array(ColorSpace::Oklch, color.to_array()),
+            color
+        );
+
+        let missing = Color::new(ColorSpace::Srgb, None, 0.5, 0.25, 1.0);
+        let from_array = Color::from_array(ColorSpace::Srgb, missing.to_array());
+        assert_eq!(from_array.components, missing.components);
+        assert_eq!(from_array.flags, ColorFlags::empty());
+    }
+
+    #[test]
+    fn default_is_transparent_black() {
+        let color = Color::default();
+        assert_eq!(color.color_space, ColorSpace::Srgb);
+        assert_eq!(color.components, Components(0.0, 0.0, 0.0));
+        assert_eq!(color.flags, ColorFlags::empty());
+        assert_eq!(color.alpha, 0.0);
+    }
+
+    #[test]
+    fn equal_colors_hash_the_same() {
+        use std::collections::{hash_map::RandomState, HashSet};
+        use std::hash::BuildHasher;
+
+        let mut set = HashSet::new();
+        set.insert(Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.5, 1.0));
+        set.insert(Color::new(ColorSpace::Srgb, 1.0, -0.0, 0.5, 1.0));
+        assert_eq!(set.len(), 1);
+
+        set.insert(Color::new(ColorSpace::DisplayP3, 1.0, 0.0, 0.5, 1.0));
+        set.insert(Color::new(ColorSpace::Srgb, 1.0, None, 0.5, 1.0));
+        assert_eq!(set.len(), 3);
+
+        let state = RandomState::new();
+        let nan = Color::new(ColorSpace::Lch, 50.0, 0.0, f32::NAN, 1.0);
+        let other_nan = Color::new(ColorSpace::Lch, 50.0, 0.0, -f32::NAN, 1.0);
+        assert_eq!(nan, other_nan);
+        assert_eq!(state.hash_one(&nan), state.hash_one(&other_nan));
+    }
+
+    #[test]
+    fn with_alpha_clears_the_none_flag() {
+        let color = Color::new(ColorSpace::Srgb, 1.0, None, 0.0, None).with_alpha(0.5);
+        assert_eq!(color.alpha, 0.5);
+        assert_eq!(color.flags, ColorFlags::C1_IS_NONE);
+    }
+
+    #[test]
+    fn with_component_and_set_none_keep_flags_consistent() {
+        let color = Color::new(ColorSpace::Lch, 50.0, None, 120.0, 1.0);
+
+        let color = color.with_component(1, 30.0);
+        assert_eq!(color.components, Components(50.0, 30.0, 120.0));
+        assert_eq!(color.flags, ColorFlags::empty());
+
+        let color = color.set_none(2);
+        assert_eq!(color.components, Components(50.0, 30.0, 0.0));
+        assert_eq!(color.flags, ColorFlags::C2_IS_NONE);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_none_out_of_bounds() {
+        Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 1.0).set_none(3);
+    }
+
+    #[test]
+    fn is_achromatic() {
+        let gray = Color::from_rgba8([128, 128, 128, 255]);
+        assert!(gray.is_achromatic(1.0e-4));
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        assert!(!red.is_achromatic(1.0e-4));
+
+        assert!(Color::new(ColorSpace::Hsl, 120.0, 0.0, 0.5, 1.0).is_achromatic(1.0e-4));
+        assert!(Color::new(ColorSpace::Hwb, 120.0, 0.6, 0.4, 1.0).is_achromatic(1.0e-4));
+        assert!(!Color::new(ColorSpace::Hwb, 120.0, 0.2, 0.4, 1.0).is_achromatic(1.0e-4));
+        assert!(Color::new(ColorSpace::Lab, 50.0, None, 0.0, 1.0).is_achromatic(1.0e-4));
+        assert!(!Color::new(ColorSpace::Oklch, 0.5, 0.1, 30.0, 1.0).is_achromatic(1.0e-4));
+
+        assert!(gray
+            .to_color_space(ColorSpace::XyzD50)
+            .is_achromatic(1.0e-4));
+        assert!(!red.to_color_space(ColorSpace::XyzD65).is_achromatic(1.0e-4));
+    }
+
+    #[test]
+    fn map() {
+        let color = Color::new(ColorSpace::Srgb, 0.5, None, 1.0, 0.75);
+        let halved = color.map(|_, v| v * 0.5);
+        assert_eq!(halved, Color::new(ColorSpace::Srgb, 0.25, None, 0.5, 0.75));
+
+        let lch = Color::new(ColorSpace::Lch, 50.0, 30.0, 120.0, 1.0);
+        let rotated = lch.map(|i, v| if i == 2 { v + 90.0 } else { v });
+        assert_eq!(rotated, Color::new(ColorSpace::Lch, 50.0, 30.0, 210.0, 1.0));
+    }
+
+    #[test]
+    fn component_kinds() {
+        assert_eq!(ColorSpace::Hsl.component_kinds()[0], ComponentKind::Hue);
+        assert_eq!(ColorSpace::Lch.component_kinds()[2], ComponentKind::Hue);
+        assert_eq!(ColorSpace::Oklch.component_kinds()[2], ComponentKind::Hue);
+        assert_eq!(
+            ColorSpace::XyzD65.component_kinds(),
+            [ComponentKind::X, ComponentKind::Y, ComponentKind::Z]
+        );
+
+        assert!(ComponentKind::Red.is_analogous_to(ComponentKind::X));
+        assert!(ComponentKind::Saturation.is_analogous_to(ComponentKind::Chroma));
+        assert!(ComponentKind::Hue.is_analogous_to(ComponentKind::Hue));
+        assert!(!ComponentKind::Hue.is_analogous_to(ComponentKind::Lightness));
+        assert!(!ComponentKind::Whiteness.is_analogous_to(ComponentKind::Whiteness));
+    }
+
+    #[test]
+    fn hue_index() {
+        assert_eq!(ColorSpace::Hsl.hue_index(), Some(0));
+        assert_eq!(ColorSpace::Hwb.hue_index(), Some(0));
+        assert_eq!(ColorSpace::Lch.hue_index(), Some(2));
+        assert_eq!(ColorSpace::Oklch.hue_index(), Some(2));
+        assert_eq!(ColorSpace::Srgb.hue_index(), None);
+        assert_eq!(ColorSpace::Oklab.hue_index(), None);
+        assert_eq!(ColorSpace::XyzD65.hue_index(), None);
+    }
+
+    #[test]
+    fn all_color_spaces() {
+        // The exhaustive match means a new color space can not be added
+        // without updating this test, which then checks `all` as well.
+        fn next(color_space: ColorSpace) -> Option<ColorSpace> {
+            Some(match color_space {
+                ColorSpace::Srgb => ColorSpace::Hsl,
+                ColorSpace::Hsl => ColorSpace::Hwb,
+                ColorSpace::Hwb => ColorSpace::Lab,
+                ColorSpace::Lab => ColorSpace::Lch,
+                ColorSpace::Lch => ColorSpace::Oklab,
+                ColorSpace::Oklab => ColorSpace::Oklch,
+                ColorSpace::Oklch => ColorSpace::SrgbLinear,
+                ColorSpace::SrgbLinear => ColorSpace::DisplayP3,
+                ColorSpace::DisplayP3 => ColorSpace::A98Rgb,
+                ColorSpace::A98Rgb => ColorSpace::ProphotoRgb,
+                ColorSpace::ProphotoRgb => ColorSpace::Rec2020,
+                ColorSpace::Rec2020 => ColorSpace::XyzD50,
+                ColorSpace::XyzD50 => ColorSpace::XyzD65,
+                ColorSpace::XyzD65 => return None,
+            })
+        }
+
+        let expected: Vec<_> =
+            std::iter::successors(Some(ColorSpace::Srgb), |&color_space| next(color_space))
+                .collect();
+        assert_eq!(ColorSpace::all(), expected.as_slice());
+        assert_eq!(ColorSpace::all().len(), 14);
+    }
+
+    #[test]
+    fn css_name_round_trip() {
+        for &color_space in ColorSpace::all() {
+            let name = color_space.css_name();
+            assert_eq!(ColorSpace::from_css_name(name), Some(color_space));
+            assert_eq!(
+                ColorSpace::from_css_name(&name.to_ascii_uppercase()),
+                Some(color_space)
+            );
+        }
+
+        assert_eq!(ColorSpace::DisplayP3.css_name(), "display-p3");
+        assert_eq!(ColorSpace::from_css_name("p3"), None);
+    }
+
+    #[test]
+    fn normalize() {
+        let hsl = Color::new(ColorSpace::Hsl, 400.0, 1.2, -0.1, 1.5).normalize();
+        assert_eq!(hsl, Color::new(ColorSpace::Hsl, 40.0, 1.0, 0.0, 1.0));
+
+        let lch = Color::new(ColorSpace::Oklch, 0.5, -0.1, -90.0, 0.5).normalize();
+        assert_eq!(lch, Color::new(ColorSpace::Oklch, 0.5, 0.0, 270.0, 0.5));
+
+        let hwb = Color::new(ColorSpace::Hwb, 720.0, 0.75, 0.5, 1.0).normalize();
+        assert_eq!(hwb, Color::new(ColorSpace::Hwb, 0.0, 0.6, 0.4, 1.0));
+
+        // Tiny negative hues wrap to 0, not 360.
+        let lch = Color::new(ColorSpace::Lch, 50.0, 30.0, -1.0e-6, 1.0).normalize();
+        assert_eq!(lch.components.2, 0.0);
+
+        // Unbounded components are left as they are.
+        let lab = Color::new(ColorSpace::Lab, 120.0, -200.0, 200.0, 1.0).normalize();
+        assert_eq!(lab, Color::new(ColorSpace::Lab, 100.0, -200.0, 200.0, 1.0));
+        let rgb = Color::new(ColorSpace::DisplayP3, 1.2, -0.1, 0.5, 1.0);
+        assert_eq!(rgb.normalize(), rgb);
+
+        let missing = Color::new(ColorSpace::Lch, 50.0, 30.0, None, None);
+        assert_eq!(missing.normalize(), missing);
+    }
+
+    #[test]
+    fn const_from_components() {
+        const BLACK: Color =
+            Color::from_components(ColorSpace::Srgb, [0.0, 0.0, 0.0], 1.0, ColorFlags::empty());
+        const GRAY: Color = Color::from_components(
+            ColorSpace::Oklch,
+            [0.5, 0.0, 0.0],
+            1.0,
+            ColorFlags::C2_IS_NONE,
+        );
+
+        assert_eq!(BLACK, Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 1.0));
+        assert_eq!(GRAY, Color::new(ColorSpace::Oklch, 0.5, 0.0, None, 1.0));
+    }
+
+    #[test]
+    fn color_can_be_indexed_and_iterated() {
+        let mut color = Color::new(ColorSpace::Lab, 50.0, 20.0, -30.0, 1.0);
+        assert_eq!([color[0], color[1], color[2]], [50.0, 20.0, -30.0]);
+
+        color[1] = 10.0;
+        assert_eq!(color.components, Components(50.0, 10.0, -30.0));
+        assert_eq!(
+            color.components_iter().collect::<Vec<_>>(),
+            [50.0, 10.0, -30.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Component index out of bounds (3)")]
+    fn color_index_out_of_bounds_panics() {
+        let color = Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let _ = color[3];
+    }
+
+    #[test]
+    fn resolve_missing() {
+        let color = Color::new(ColorSpace::Oklch, None, 0.1, None, 0.5);
+        assert!(color.has_missing());
+
+        let resolved = color.resolve_missing();
+        assert!(!resolved.has_missing());
+        assert_eq!(resolved.flags, ColorFlags::empty());
+        assert_eq!(resolved.to_array(), [0.0, 0.1, 0.0, 0.5]);
+
+        let alpha = Color::new(ColorSpace::Srgb, 1.0, 0.5, 0.0, None);
+        assert!(alpha.has_missing());
+        assert_eq!(alpha.resolve_missing().to_array(), [1.0, 0.5, 0.0, 0.0]);
+
+        let complete = Color::new(ColorSpace::Srgb, 1.0, 0.5, 0.0, 1.0);
+        assert!(!complete.has_missing());
+        assert_eq!(complete.clone().resolve_missing(), complete);
+    }
+
+    #[test]
+    fn reinterpret() {
+        let color = Color::new(ColorSpace::Srgb, 0.25, None, 0.75, 0.5);
+        let lab = color.clone().reinterpret(ColorSpace::Lab);
+
+        assert_eq!(lab.color_space, ColorSpace::Lab);
+        assert_eq!(lab.components, color.components);
+        assert_eq!(lab.flags, color.flags);
+        assert_eq!(lab.alpha, color.alpha);
+        assert_ne!(lab, color.to_color_space(ColorSpace::Lab));
+    }
+
+    #[test]
+    fn clamp_alpha() {
+        let opaque = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.5).clamp_alpha();
+        assert_eq!(opaque.alpha, 1.0);
+
+        let transparent = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, -0.2).clamp_alpha();
+        assert_eq!(transparent.alpha, 0.0);
+
+        let translucent = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5);
+        assert_eq!(translucent.clone().clamp_alpha(), translucent);
+
+        let missing = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, None).clamp_alpha();
+        assert!(missing.flags.contains(ColorFlags::ALPHA_IS_NONE));
+    }
+
+    #[test]
+    fn clamp() {
+        let srgb = Color::new(ColorSpace::Srgb, 1.2, -0.1, None, 1.5);
+        assert_eq!(
+            srgb.clamp(),
+            Color::new(ColorSpace::Srgb, 1.0, 0.0, None, 1.0)
+        );
+
+        let lab = Color::new(ColorSpace::Lab, -5.0, -150.0, 200.0, -0.5);
+        assert_eq!(
+            lab.clamp(),
+            Color::new(ColorSpace::Lab, 0.0, -150.0, 200.0, 0.0)
+        );
+
+        let lch = Color::new(ColorSpace::Lch, 120.0, -10.0, 400.0, 1.0);
+        assert_eq!(
+            lch.clamp(),
+            Color::new(ColorSpace::Lch, 120.0, 0.0, 400.0, 1.0)
+        );
+
+        let hwb = Color::new(ColorSpace::Hwb, -30.0, 1.5, -0.5, 1.0);
+        assert_eq!(
+            hwb.clamp(),
+            Color::new(ColorSpace::Hwb, -30.0, 1.0, 0.0, 1.0)
+        );
+
+        let xyz = Color::new(ColorSpace::XyzD65, -0.1, 1.5, 2.0, 1.0);
+        assert_eq!(xyz.clamp(), xyz);
+    }
+
+    #[test]
+    fn none_accessors() {
+        let color = Color::new(ColorSpace::Lch, None, 30.0, None, 1.0);
+        assert!(color.is_none(0));
+        assert!(!color.is_none(1));
+        assert!(color.is_none(2));
+        assert!(!color.is_none(3));
+        assert_eq!(color.none_mask(), [true, false, true, false]);
+
+        let color = Color::new(ColorSpace::Srgb, 1.0, None, 0.0, None);
+        assert_eq!(color.none_mask(), [false, true, false, true]);
+
+        let color = Color::new(ColorSpace::Srgb, 1.0, 0.5, 0.0, 1.0);
+        assert_eq!(color.none_mask(), [false; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Component index out of bounds (4)")]
+    fn is_none_out_of_bounds_panics() {
+        let _ = Color::transparent().is_none(4);
+    }
 }
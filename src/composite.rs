@@ -0,0 +1,367 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{Color, ColorFlags, ColorSpace, Components};
+
+/// The alpha of a color for compositing, where a missing alpha is treated as
+/// fully opaque, like [`Color::premultiply`] does.
+fn compositing_alpha(color: &Color) -> f32 {
+    if color.flags.contains(ColorFlags::ALPHA_IS_NONE) {
+        1.0
+    } else {
+        color.alpha.clamp(0.0, 1.0)
+    }
+}
+
+/// How the colors of a source and a backdrop are mixed by [`Color::blend`].
+/// <https://drafts.fxtf.org/compositing/#blending>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BlendMode {
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+/// The luminosity of an sRGB color, as used by the non-separable blend modes.
+/// <https://drafts.fxtf.org/compositing/#blendingnonseparable>
+fn lum([red, green, blue]: [f32; 3]) -> f32 {
+    0.3 * red + 0.59 * green + 0.11 * blue
+}
+
+/// Bring the channels of `color` into `[0, 1]` while keeping its luminosity.
+fn clip_color(color: [f32; 3]) -> [f32; 3] {
+    let l = lum(color);
+    let n = color[0].min(color[1]).min(color[2]);
+    let x = color[0].max(color[1]).max(color[2]);
+
+    let mut color = color;
+    if n < 0.0 {
+        color = color.map(|c| l + (c - l) * l / (l - n));
+    }
+    if x > 1.0 {
+        color = color.map(|c| l + (c - l) * (1.0 - l) / (x - l));
+    }
+    color
+}
+
+fn set_lum(color: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(color);
+    clip_color(color.map(|c| c + d))
+}
+
+fn sat(color: [f32; 3]) -> f32 {
+    color[0].max(color[1]).max(color[2]) - color[0].min(color[1]).min(color[2])
+}
+
+/// Scale the channels of `color` so its saturation is `s`, keeping the order
+/// of the channels.
+fn set_sat(color: [f32; 3], s: f32) -> [f32; 3] {
+    let mut order = [0, 1, 2];
+    order.sort_by(|&a, &b| color[a].total_cmp(&color[b]));
+    let [min, mid, max] = order;
+
+    let mut result = [0.0; 3];
+    if color[max] > color[min] {
+        result[mid] = (color[mid] - color[min]) * s / (color[max] - color[min]);
+        result[max] = s;
+    }
+    result
+}
+
+impl BlendMode {
+    /// Blend the backdrop, `cb`, with the source, `cs`. The separable modes
+    /// blend each channel on its own.
+    fn blend(self, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+        match self {
+            Self::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+            Self::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+            Self::Color => set_lum(cs, lum(cb)),
+            Self::Luminosity => set_lum(cb, lum(cs)),
+            _ => [0, 1, 2].map(|i| self.blend_channel(cb[i], cs[i])),
+        }
+    }
+
+    /// Blend a single channel of the backdrop, `cb`, with the same channel of
+    /// the source, `cs`.
+    /// <https://drafts.fxtf.org/compositing/#blendingseparable>
+    fn blend_channel(self, cb: f32, cs: f32) -> f32 {
+        match self {
+            Self::Multiply => cb * cs,
+            Self::Screen => cb + cs - cb * cs,
+            Self::Overlay => Self::HardLight.blend_channel(cs, cb),
+            Self::Darken => cb.min(cs),
+            Self::Lighten => cb.max(cs),
+            Self::ColorDodge => {
+                if cb == 0.0 {
+                    0.0
+                } else if cs == 1.0 {
+                    1.0
+                } else {
+                    (cb / (1.0 - cs)).min(1.0)
+                }
+            }
+            Self::ColorBurn => {
+                if cb == 1.0 {
+                    1.0
+                } else if cs == 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - cb) / cs).min(1.0)
+                }
+            }
+            Self::HardLight => {
+                if cs <= 0.5 {
+                    Self::Multiply.blend_channel(cb, 2.0 * cs)
+                } else {
+                    Self::Screen.blend_channel(cb, 2.0 * cs - 1.0)
+                }
+            }
+            Self::SoftLight => {
+                if cs <= 0.5 {
+                    cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+                } else {
+                    let d = if cb <= 0.25 {
+                        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                    } else {
+                        cb.sqrt()
+                    };
+                    cb + (2.0 * cs - 1.0) * (d - cb)
+                }
+            }
+            Self::Difference => (cb - cs).abs(),
+            Self::Exclusion => cb + cs - 2.0 * cb * cs,
+            Self::Hue | Self::Saturation | Self::Color | Self::Luminosity => {
+                unreachable!("{self:?} is not a separable blend mode")
+            }
+        }
+    }
+}
+
+impl Color {
+    /// Blend this color, the source, with `backdrop` using `mode`. The colors
+    /// are blended in sRGB after clamping them to its gamut, and mixed
+    /// with the unblended source by the alpha of the backdrop. The result
+    /// keeps the alpha of the source and is in the color space of the source,
+    /// ready to be composited over the backdrop with [`Color::over`].
+    /// <https://drafts.fxtf.org/compositing/#blending>
+    pub fn blend(&self, backdrop: &Color, mode: BlendMode) -> Color {
+        let source = self.clip_to_gamut(ColorSpace::Srgb).resolved_components();
+        let backdrop_alpha = compositing_alpha(backdrop);
+        let backdrop = backdrop
+            .clip_to_gamut(ColorSpace::Srgb)
+            .resolved_components();
+
+        let blended = mode.blend(backdrop, source);
+        let [red, green, blue] =
+            [0, 1, 2].map(|i| (1.0 - backdrop_alpha) * source[i] + backdrop_alpha * blended[i]);
+
+        Color {
+            components: Components(red, green, blue),
+            flags: self.flags & ColorFlags::ALPHA_IS_NONE,
+            color_space: ColorSpace::Srgb,
+            alpha: self.alpha,
+        }
+        .to_color_space(self.color_space)
+    }
+
+    /// Composite this color over `background` with the Porter-Duff "source
+    /// over" operator. The colors are premultiplied and combined in linear
+    /// sRGB, and the result is converted back to the color space of this
+    /// color. Missing components are treated as 0 and a missing alpha as 1.
+    /// <https://drafts.fxtf.org/compositing/#porterduffcompositingoperators_srcover>
+    pub fn over(&self, background: &Color) -> Color {
+        let source_alpha = compositing_alpha(self);
+        if source_alpha >= 1.0 {
+            return self.clone();
+        }
+
+        if source_alpha <= 0.0 {
+            return background.to_color_space(self.color_space);
+        }
+
+        // Can not be 0, because the source alpha is not.
+        let backdrop_alpha = compositing_alpha(background);
+        let alpha = source_alpha + backdrop_alpha * (1.0 - source_alpha);
+
+        let source = self.to_linear_srgb().resolved_components();
+        let backdrop = background.to_linear_srgb().resolved_components();
+        let [red, green, blue] = [0, 1, 2].map(|i| {
+            let premultiplied =
+                source[i] * source_alpha + backdrop[i] * backdrop_alpha * (1.0 - source_alpha);
+            premultiplied / alpha
+        });
+
+        Color {
+            components: Components(red, green, blue),
+            flags: ColorFlags::empty(),
+            color_space: ColorSpace::SrgbLinear,
+            alpha,
+        }
+        .to_color_space(self.color_space)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! assert_components {
+        ($color:expr, $c0:expr, $c1:expr, $c2:expr, $alpha:expr) => {{
+            let color = &$color;
+            for (actual, expected) in [
+                (color.components.0, $c0),
+                (color.components.1, $c1),
+                (color.components.2, $c2),
+                (color.alpha, $alpha),
+            ] {
+                assert!(
+                    (actual - expected).abs() < 1.0e-3,
+                    "{actual} is not equal to {expected}"
+                );
+            }
+        }};
+    }
+
+    #[test]
+    fn opaque_source_covers_the_background() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        for background in [
+            Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0),
+            Color::new(ColorSpace::Lab, 50.0, 20.0, -30.0, 0.5),
+            Color::transparent(),
+        ] {
+            assert_eq!(red.over(&background), red);
+        }
+    }
+
+    #[test]
+    fn half_transparent_red_over_white_is_pink() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5);
+        let white = Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 1.0);
+
+        // (1, 0.5, 0.5) in linear light.
+        let pink = red.over(&white);
+        assert_eq!(pink.color_space, ColorSpace::Srgb);
+        assert_components!(pink, 1.0, 0.73536, 0.73536, 1.0);
+    }
+
+    #[test]
+    fn alpha_is_combined() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5);
+        let blue = Color::new(ColorSpace::SrgbLinear, 0.0, 0.0, 1.0, 0.5);
+
+        // 0.5 + 0.5 * (1 - 0.5) = 0.75 and each color contributes 0.5 and 0.25
+        // of the premultiplied result.
+        let result = red.over(&blue).to_color_space(ColorSpace::SrgbLinear);
+        assert_components!(result, 2.0 / 3.0, 0.0, 1.0 / 3.0, 0.75);
+    }
+
+    #[test]
+    fn blend_multiply_and_screen() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let gray = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.5, 1.0);
+
+        assert_components!(red.blend(&gray, BlendMode::Multiply), 0.5, 0.0, 0.0, 1.0);
+        assert_components!(red.blend(&gray, BlendMode::Screen), 1.0, 0.5, 0.5, 1.0);
+
+        let color = Color::new(ColorSpace::Srgb, 0.2, 0.4, 0.6, 1.0);
+        let backdrop = Color::new(ColorSpace::Srgb, 0.5, 0.25, 0.8, 1.0);
+        assert_components!(
+            color.blend(&backdrop, BlendMode::Multiply),
+            0.1,
+            0.1,
+            0.48,
+            1.0
+        );
+        assert_components!(
+            color.blend(&backdrop, BlendMode::Screen),
+            0.6,
+            0.55,
+            0.92,
+            1.0
+        );
+    }
+
+    #[test]
+    fn blend_modes() {
+        use BlendMode as B;
+
+        let source = Color::new(ColorSpace::Srgb, 0.2, 0.6, 1.0, 0.5);
+        let backdrop = Color::new(ColorSpace::Srgb, 0.5, 0.25, 0.8, 1.0);
+
+        let expected = [
+            (B::Overlay, [0.2, 0.3, 1.0]),
+            (B::Darken, [0.2, 0.25, 0.8]),
+            (B::Lighten, [0.5, 0.6, 1.0]),
+            (B::ColorDodge, [0.625, 0.625, 1.0]),
+            (B::ColorBurn, [0.0, 0.0, 0.8]),
+            (B::HardLight, [0.2, 0.4, 1.0]),
+            (B::SoftLight, [0.35, 0.3, 0.8944]),
+            (B::Difference, [0.3, 0.35, 0.2]),
+            (B::Exclusion, [0.5, 0.55, 0.2]),
+        ];
+        for (mode, [red, green, blue]) in expected {
+            let result = source.blend(&backdrop, mode);
+            assert_components!(result, red, green, blue, 0.5);
+        }
+    }
+
+    #[test]
+    fn non_separable_blend_modes() {
+        use BlendMode as B;
+
+        let source = Color::new(ColorSpace::Srgb, 0.2, 0.6, 1.0, 1.0);
+        let backdrop = Color::new(ColorSpace::Srgb, 0.5, 0.25, 0.8, 1.0);
+
+        let expected = [
+            (B::Hue, [0.16275, 0.43775, 0.71275]),
+            (B::Saturation, [0.55205, 0.18841, 0.98841]),
+            (B::Color, [0.0615, 0.4615, 0.8615]),
+            (B::Luminosity, [0.6385, 0.3885, 0.9385]),
+        ];
+        for (mode, [red, green, blue]) in expected {
+            let result = source.blend(&backdrop, mode);
+            assert_components!(result, red, green, blue, 1.0);
+        }
+
+        // A gray has no hue or saturation to give.
+        let gray = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.5, 1.0);
+        let result = gray.blend(&backdrop, B::Hue);
+        let l = lum(backdrop.resolved_components());
+        assert_components!(result, l, l, l, 1.0);
+    }
+
+    #[test]
+    fn blend_with_transparent_backdrop_keeps_the_source() {
+        let source = Color::new(ColorSpace::Lab, 50.0, 20.0, -30.0, 0.75);
+        let result = source.blend(&Color::transparent(), BlendMode::Multiply);
+        assert_eq!(result.color_space, ColorSpace::Lab);
+        assert!(result.approx_eq(&source, 1.0e-3));
+    }
+
+    #[test]
+    fn transparent_edge_cases() {
+        let lab = Color::new(ColorSpace::Lab, 50.0, 20.0, -30.0, 0.5);
+
+        let result = Color::transparent().over(&lab);
+        assert_eq!(result.color_space, ColorSpace::Srgb);
+        assert!(result.approx_eq(&lab.to_color_space(ColorSpace::Srgb), 1.0e-5));
+
+        let clear = Color::new(ColorSpace::Oklch, 0.5, 0.1, 30.0, 0.0);
+        let result = clear.over(&Color::transparent());
+        assert_eq!(result.color_space, ColorSpace::Oklch);
+        assert_eq!(result.alpha, 0.0);
+        assert!(result.components.0.abs() < 1.0e-5);
+    }
+}
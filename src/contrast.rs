@@ -0,0 +1,151 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{Color, ColorSpace};
+
+impl Color {
+    /// The relative luminance of the color, computed from linear-light sRGB.
+    /// <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>
+    pub fn relative_luminance(&self) -> f32 {
+        let [red, green, blue] = self.resolved_components_in(ColorSpace::SrgbLinear);
+        0.2126 * red + 0.7152 * green + 0.0722 * blue
+    }
+
+    /// The WCAG 2 contrast ratio between this color and `other`, ranging from
+    /// 1 (no contrast) to 21 (black on white). The order of the colors does not
+    /// matter.
+    /// <https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio>
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let l1 = self.relative_luminance();
+        let l2 = other.relative_luminance();
+        let (lighter, darker) = if l1 >= l2 { (l1, l2) } else { (l2, l1) };
+
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// The APCA lightness contrast (Lc) of `text` on `background`, using the
+    /// 0.0.98G constants. Dark text on a light background gives a positive
+    /// value up to about 106, and light text on a dark background gives a
+    /// negative value down to about -108.
+    /// <https://github.com/Myndex/apca-w3>
+    pub fn apca_contrast(text: &Color, background: &Color) -> f32 {
+        const MAIN_TRC: f32 = 2.4;
+
+        const NORM_BG: f32 = 0.56;
+        const NORM_TXT: f32 = 0.57;
+        const REV_TXT: f32 = 0.62;
+        const REV_BG: f32 = 0.65;
+
+        const BLACK_THRESHOLD: f32 = 0.022;
+        const BLACK_CLAMP: f32 = 1.414;
+        const SCALE: f32 = 1.14;
+        const LOW_OFFSET: f32 = 0.027;
+        const LOW_CLIP: f32 = 0.1;
+        const DELTA_Y_MIN: f32 = 0.0005;
+
+        let screen_luminance = |color: &Color| {
+            let [red, green, blue] = color
+                .resolved_components_in(ColorSpace::Srgb)
+                .map(|value| value.clamp(0.0, 1.0).powf(MAIN_TRC));
+            let y = 0.2126729 * red + 0.7151522 * green + 0.0721750 * blue;
+
+            // Soft clamp near black.
+            if y < BLACK_THRESHOLD {
+                y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+            } else {
+                y
+            }
+        };
+
+        let text = screen_luminance(text);
+        let background = screen_luminance(background);
+
+        if (background - text).abs() < DELTA_Y_MIN {
+            return 0.0;
+        }
+
+        let lc = if background > text {
+            // Dark text on a light background.
+            let sapc = (background.powf(NORM_BG) - text.powf(NORM_TXT)) * SCALE;
+            if sapc < LOW_CLIP {
+                0.0
+            } else {
+                sapc - LOW_OFFSET
+            }
+        } else {
+            // Light text on a dark background.
+            let sapc = (background.powf(REV_BG) - text.powf(REV_TXT)) * SCALE;
+            if sapc > -LOW_CLIP {
+                0.0
+            } else {
+                sapc + LOW_OFFSET
+            }
+        };
+
+        lc * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_luminance() {
+        let black = Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let white = Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+
+        assert_eq!(black.relative_luminance(), 0.0);
+        assert!((white.relative_luminance() - 1.0).abs() < 1.0e-4);
+        assert!((red.relative_luminance() - 0.2126).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn contrast_ratio() {
+        let black = Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let white = Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 1.0);
+
+        assert!((black.contrast_ratio(&white) - 21.0).abs() < 1.0e-3);
+        assert!((white.contrast_ratio(&black) - 21.0).abs() < 1.0e-3);
+        assert_eq!(white.contrast_ratio(&white), 1.0);
+
+        // Other color spaces are converted first.
+        let lab_white = Color::new(ColorSpace::Lab, 100.0, 0.0, 0.0, 1.0);
+        assert!((black.contrast_ratio(&lab_white) - 21.0).abs() < 1.0e-2);
+
+        let gray = Color::from_rgba8([0x76, 0x76, 0x76, 0xff]);
+        assert!((gray.contrast_ratio(&white) - 4.54).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn apca_contrast() {
+        let pairs = [
+            ([0x88, 0x88, 0x88], [0xff, 0xff, 0xff], 63.0565),
+            ([0xff, 0xff, 0xff], [0x88, 0x88, 0x88], -68.5415),
+            ([0x00, 0x00, 0x00], [0xaa, 0xaa, 0xaa], 58.1463),
+            ([0xaa, 0xaa, 0xaa], [0x00, 0x00, 0x00], -56.2411),
+            ([0x00, 0x00, 0x00], [0xff, 0xff, 0xff], 106.0407),
+            ([0xff, 0xff, 0xff], [0x00, 0x00, 0x00], -107.8847),
+            ([0x11, 0x22, 0x33], [0xdd, 0xee, 0xff], 91.6683),
+        ];
+
+        for ([r1, g1, b1], [r2, g2, b2], expected) in pairs {
+            let text = Color::from_rgba8([r1, g1, b1, 0xff]);
+            let background = Color::from_rgba8([r2, g2, b2, 0xff]);
+            let lc = Color::apca_contrast(&text, &background);
+            assert!(
+                (lc - expected).abs() < 1.0e-2,
+                "{lc} is not equal to {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn apca_contrast_of_similar_colors_is_zero() {
+        let gray = Color::from_rgba8([0x80, 0x80, 0x80, 0xff]);
+        let near = Color::from_rgba8([0x84, 0x84, 0x84, 0xff]);
+
+        assert_eq!(Color::apca_contrast(&gray, &gray), 0.0);
+        assert_eq!(Color::apca_contrast(&gray, &near), 0.0);
+    }
+}
@@ -1,9 +1,15 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
 use crate::{
-    color::{Color, ColorSpace, Components},
-    model::{ColorSpaceModel, WhitePoint},
+    color::{Color, ColorFlags, ColorSpace, Components},
+    model::{ColorSpaceModel, Oklab, Oklch, WhitePoint, Xyz},
     Hsl, Hwb,
 };
-use crate::{Lab, Lch, Srgb, SrgbLinear, XyzD50, XyzD65, D50};
+use crate::{
+    A98Rgb, A98RgbLinear, DisplayP3, DisplayP3Linear, Lab, Lch, ProphotoRgb, ProphotoRgbLinear,
+    Rec2020, Rec2020Linear, Srgb, SrgbLinear, XyzD50, XyzD65, D50, D65,
+};
+use alloc::vec::Vec;
 
 type Transform = euclid::default::Transform3D<f32>;
 type Vector = euclid::default::Vector3D<f32>;
@@ -13,14 +19,364 @@ fn transform(from: &Components, mat: &Transform) -> Components {
     Components(result.x, result.y, result.z)
 }
 
+#[rustfmt::skip]
+const SRGB_TO_XYZ_D65: Transform = Transform::new(
+    0.4123907992659595,  0.21263900587151036, 0.01933081871559185, 0.0,
+    0.35758433938387796, 0.7151686787677559,  0.11919477979462599, 0.0,
+    0.1804807884018343,  0.07219231536073371, 0.9505321522496606,  0.0,
+    0.0,                 0.0,                 0.0,                 1.0,
+);
+
+#[rustfmt::skip]
+const DISPLAY_P3_TO_XYZ_D65: Transform = Transform::new(
+    0.4865709486482162,  0.2289745640697488, 0.0,                 0.0,
+    0.26566769316909306, 0.6917385218365064, 0.04511338185890264, 0.0,
+    0.1982172852343625,  0.079286914093745,  1.043944368900976,   0.0,
+    0.0,                 0.0,                0.0,                 1.0,
+);
+
+#[rustfmt::skip]
+const A98_RGB_TO_XYZ_D65: Transform = Transform::new(
+    0.5766690429101305, 0.29734497525053605, 0.02703136138641234, 0.0,
+    0.1855582379065463, 0.6273635662554661,  0.07068885253582723, 0.0,
+    0.1882286462349947, 0.07529145849399788, 0.9913375368376388,  0.0,
+    0.0,                0.0,                 0.0,                 1.0,
+);
+
+#[rustfmt::skip]
+const REC2020_TO_XYZ_D65: Transform = Transform::new(
+    0.6369580483012914,  0.2627002120112671,  0.0,                  0.0,
+    0.14461690358620832, 0.6779980715188708,  0.028072693049087428, 0.0,
+    0.1688809751641721,  0.05930171646986196, 1.060985057710791,    0.0,
+    0.0,                 0.0,                 0.0,                  1.0,
+);
+
+#[rustfmt::skip]
+const XYZ_D65_TO_SRGB: Transform = Transform::new(
+     3.2409699419045213, -0.9692436362808798,  0.05563007969699361, 0.0,
+    -1.5373831775700935,  1.8759675015077206, -0.20397695888897657, 0.0,
+    -0.4986107602930033,  0.04155505740717561, 1.0569715142428786,  0.0,
+     0.0,                 0.0,                 0.0,                 1.0,
+);
+
+#[rustfmt::skip]
+const XYZ_D65_TO_DISPLAY_P3: Transform = Transform::new(
+     2.493496911941425,   -0.8294889695615747,   0.03584583024378447, 0.0,
+    -0.9313836179191239,   1.7626640603183463,  -0.07617238926804182, 0.0,
+    -0.40271078445071684,  0.023624685841943577, 0.9568845240076872,  0.0,
+     0.0,                  0.0,                  0.0,                 1.0,
+);
+
+#[rustfmt::skip]
+const XYZ_D65_TO_A98_RGB: Transform = Transform::new(
+     2.0415879038107465,  -0.9692436362808795,   0.013444280632031142, 0.0,
+    -0.5650069742788596,   1.8759675015077202,  -0.11836239223101838,  0.0,
+    -0.34473135077832956,  0.04155505740717557,  1.0151749943912054,   0.0,
+     0.0,                  0.0,                  0.0,                  1.0,
+);
+
+#[rustfmt::skip]
+const XYZ_D65_TO_REC2020: Transform = Transform::new(
+     1.716651187971268,  -0.666684351832489,   0.017639857445311, 0.0,
+    -0.355670783776392,   1.616481236634939,  -0.042770613257809, 0.0,
+    -0.253366281373660,   0.0157685458139111,  0.942103121235474, 0.0,
+     0.0,                 0.0,                 0.0,               1.0,
+);
+
+#[rustfmt::skip]
+const PROPHOTO_RGB_TO_XYZ_D50: Transform = Transform::new(
+    0.7977666449006423,  0.2880748288194013,  0.0,                0.0,
+    0.13518129740053308, 0.711835234241873,   0.0,                0.0,
+    0.0313477341283922,  0.00008993693872564, 0.8251046025104602, 0.0,
+    0.0,                 0.0,                 0.0,                1.0,
+);
+
+#[rustfmt::skip]
+const XYZ_D50_TO_PROPHOTO_RGB: Transform = Transform::new(
+     1.3457868816471583,  -0.5446307051249019,  0.0,                0.0,
+    -0.25557208737979464,  1.5082477428451468,  0.0,                0.0,
+    -0.05110186497554526,  0.02052744743642139, 1.2119675456389452, 0.0,
+     0.0,                  0.0,                 0.0,                1.0,
+);
+
+/// Build the Bradford chromatic adaptation matrix from the `src` to the `dst`
+/// white point. The matrix is calculated in double precision.
+/// <http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html>
+const fn bradford_adaptation(src: &Components, dst: &Components) -> Transform {
+    #[rustfmt::skip]
+    const BRADFORD: [[f64; 3]; 3] = [
+        [ 0.8951,  0.2664, -0.1614],
+        [-0.7502,  1.7135,  0.0367],
+        [ 0.0389, -0.0685,  1.0296],
+    ];
+
+    #[rustfmt::skip]
+    const BRADFORD_INVERSE: [[f64; 3]; 3] = [
+        [ 0.9869929054667123,   -0.14705425642099013, 0.15996265166373122],
+        [ 0.43230526972339456,   0.5183602715367776,  0.0492912282128556 ],
+        [-0.008528664575177328,  0.04004282165408487, 0.9684866957875501 ],
+    ];
+
+    let src = [src.0 as f64, src.1 as f64, src.2 as f64];
+    let dst = [dst.0 as f64, dst.1 as f64, dst.2 as f64];
+
+    // Scale the cone responses of the source to those of the destination.
+    let mut scale = [0.0; 3];
+    let mut i = 0;
+    while i < 3 {
+        let row = BRADFORD[i];
+        let src_cone = row[0] * src[0] + row[1] * src[1] + row[2] * src[2];
+        let dst_cone = row[0] * dst[0] + row[1] * dst[1] + row[2] * dst[2];
+        scale[i] = dst_cone / src_cone;
+        i += 1;
+    }
+
+    // m = BRADFORD_INVERSE * diag(scale) * BRADFORD
+    let mut m = [[0.0; 3]; 3];
+    let mut row = 0;
+    while row < 3 {
+        let mut col = 0;
+        while col < 3 {
+            let mut k = 0;
+            while k < 3 {
+                m[row][col] += BRADFORD_INVERSE[row][k] * scale[k] * BRADFORD[k][col];
+                k += 1;
+            }
+            col += 1;
+        }
+        row += 1;
+    }
+
+    // Transposed, because euclid multiplies row vectors.
+    #[rustfmt::skip]
+    let mat = Transform::new(
+        m[0][0] as f32, m[1][0] as f32, m[2][0] as f32, 0.0,
+        m[0][1] as f32, m[1][1] as f32, m[2][1] as f32, 0.0,
+        m[0][2] as f32, m[1][2] as f32, m[2][2] as f32, 0.0,
+        0.0,            0.0,            0.0,            1.0,
+    );
+
+    mat
+}
+
+/// Adapt XYZ components relative to the `S` white point so that they are
+/// relative to the `D` white point, using the Bradford transform.
+pub fn chromatic_adapt<S: WhitePoint, D: WhitePoint>(from: &Components) -> Components {
+    let mat = const { bradford_adaptation(&S::WHITE_POINT, &D::WHITE_POINT) };
+    transform(from, &mat)
+}
+
+#[rustfmt::skip]
+pub(crate) const XYZ_TO_LMS: Transform = Transform::new(
+     0.8190224379967030, 0.0329836539323885, 0.0481771893596242, 0.0,
+     0.3619062600528904, 0.9292868615863434, 0.2642395317527308, 0.0,
+    -0.1288737815209879, 0.0361446663506424, 0.6335478284694309, 0.0,
+     0.0,                0.0,                0.0,                1.0,
+);
+
+#[rustfmt::skip]
+pub(crate) const LMS_TO_OKLAB: Transform = Transform::new(
+     0.2104542683093140,  1.9779985324311684,  0.0259040424655478, 0.0,
+     0.7936177747023054, -2.4285922420485799,  0.7827717124575296, 0.0,
+    -0.0040720430116193,  0.4505937096174110, -0.8086757549230774, 0.0,
+     0.0,                 0.0,                 0.0,                1.0,
+);
+
+/// [`SRGB_TO_XYZ_D65`] followed by [`XYZ_TO_LMS`], so linear sRGB can be
+/// converted to Oklab without going through XYZ.
+#[rustfmt::skip]
+const SRGB_TO_LMS: Transform = Transform::new(
+    0.41222146947076305, 0.21190349581782522, 0.08830245919005643, 0.0,
+    0.5363325372617348,  0.6806995506452344,  0.2817188391361215,  0.0,
+    0.05144599326750221, 0.10739695353694056, 0.6299787016738222,  0.0,
+    0.0,                 0.0,                 0.0,                 1.0,
+);
+
+#[rustfmt::skip]
+pub(crate) const OKLAB_TO_LMS: Transform = Transform::new(
+    1.0,                 1.0,                 1.0,                0.0,
+    0.3963377773761749, -0.1055613458156586, -0.0894841775298119, 0.0,
+    0.2158037573099136, -0.0638541728258133, -1.2914855480194092, 0.0,
+    0.0,                 0.0,                 0.0,                1.0,
+);
+
+#[rustfmt::skip]
+pub(crate) const LMS_TO_XYZ: Transform = Transform::new(
+     1.2268798758459243, -0.0405757452148008, -0.0763729366746601, 0.0,
+    -0.5578149944602171,  1.1122868032803170, -0.4214933324022432, 0.0,
+     0.2813910456659647, -0.0717110580655164,  1.5869240198367816, 0.0,
+     0.0,                 0.0,                 0.0,                1.0,
+);
+
+/// The matrix from linear light to XYZ-D65 for RGB color spaces with a D65
+/// white point.
+fn linear_rgb_to_xyz_d65(color_space: ColorSpace) -> Option<Transform> {
+    match color_space {
+        ColorSpace::Srgb | ColorSpace::SrgbLinear => Some(SRGB_TO_XYZ_D65),
+        ColorSpace::DisplayP3 => Some(DISPLAY_P3_TO_XYZ_D65),
+        ColorSpace::A98Rgb => Some(A98_RGB_TO_XYZ_D65),
+        ColorSpace::Rec2020 => Some(REC2020_TO_XYZ_D65),
+        _ => None,
+    }
+}
+
+/// The matrix from XYZ-D65 to linear light for RGB color spaces with a D65
+/// white point.
+fn xyz_d65_to_linear_rgb(color_space: ColorSpace) -> Option<Transform> {
+    match color_space {
+        ColorSpace::Srgb | ColorSpace::SrgbLinear => Some(XYZ_D65_TO_SRGB),
+        ColorSpace::DisplayP3 => Some(XYZ_D65_TO_DISPLAY_P3),
+        ColorSpace::A98Rgb => Some(XYZ_D65_TO_A98_RGB),
+        ColorSpace::Rec2020 => Some(XYZ_D65_TO_REC2020),
+        _ => None,
+    }
+}
+
+/// A single matrix that converts linear light between two RGB color spaces
+/// that share the D65 white point, so the conversion does not have to make a
+/// round trip through XYZ-D50.
+fn linear_rgb_transform(from: ColorSpace, to: ColorSpace) -> Option<Transform> {
+    Some(linear_rgb_to_xyz_d65(from)?.then(&xyz_d65_to_linear_rgb(to)?))
+}
+
+/// The 3x3 part of `mat` in row-major order, as written in the spec. The
+/// matrices are stored transposed, because euclid multiplies row vectors.
+#[rustfmt::skip]
+fn row_major(mat: &Transform) -> [f32; 9] {
+    [
+        mat.m11, mat.m21, mat.m31,
+        mat.m12, mat.m22, mat.m32,
+        mat.m13, mat.m23, mat.m33,
+    ]
+}
+
+impl ColorSpace {
+    /// The row-major matrix that converts linear light in this RGB color space
+    /// to XYZ, or `None` if this is not an RGB color space. ProPhoto RGB
+    /// converts to XYZ-D50 and the other RGB color spaces to XYZ-D65. sRGB and
+    /// linear sRGB share a matrix.
+    pub fn to_xyz_matrix(&self) -> Option<[f32; 9]> {
+        let mat = match self {
+            ColorSpace::ProphotoRgb => PROPHOTO_RGB_TO_XYZ_D50,
+            _ => linear_rgb_to_xyz_d65(*self)?,
+        };
+        Some(row_major(&mat))
+    }
+
+    /// The row-major matrix that converts XYZ to linear light in this RGB
+    /// color space, the inverse of [`ColorSpace::to_xyz_matrix`].
+    pub fn from_xyz_matrix(&self) -> Option<[f32; 9]> {
+        let mat = match self {
+            ColorSpace::ProphotoRgb => XYZ_D50_TO_PROPHOTO_RGB,
+            _ => xyz_d65_to_linear_rgb(*self)?,
+        };
+        Some(row_major(&mat))
+    }
+}
+
 impl Color {
+    /// Convert the color to another color space. Missing components are
+    /// treated as 0 during the conversion and carried forward to the result
+    /// if the destination has an analogous component.
+    /// <https://drafts.csswg.org/css-color-4/#color-conversion>
     pub fn to_color_space(&self, color_space: ColorSpace) -> Color {
-        use ColorSpace as C;
+        let rgb_transform = linear_rgb_transform(self.color_space, color_space);
+        self.to_color_space_with(color_space, rgb_transform.as_ref())
+    }
 
+    /// Convert the color to another color space, using `rgb_transform` as a
+    /// shortcut between RGB color spaces if it is available.
+    fn to_color_space_with(
+        &self,
+        color_space: ColorSpace,
+        rgb_transform: Option<&Transform>,
+    ) -> Color {
         if self.color_space == color_space {
             return self.clone();
         }
 
+        const COMPONENT_FLAGS: [ColorFlags; 3] = [
+            ColorFlags::C0_IS_NONE,
+            ColorFlags::C1_IS_NONE,
+            ColorFlags::C2_IS_NONE,
+        ];
+
+        let mut resolved = self.clone();
+        for (i, flag) in COMPONENT_FLAGS.into_iter().enumerate() {
+            if self.flags.contains(flag) {
+                resolved.components[i] = 0.0;
+            }
+        }
+        if self.flags.contains(ColorFlags::ALPHA_IS_NONE) {
+            resolved.alpha = 0.0;
+        }
+        resolved.flags = ColorFlags::empty();
+
+        let mut result = resolved.convert_components(color_space, rgb_transform);
+
+        let from = self.color_space.component_kinds();
+        let to = color_space.component_kinds();
+        for (i, flag) in COMPONENT_FLAGS.into_iter().enumerate() {
+            if !self.flags.contains(flag) {
+                continue;
+            }
+            if let Some(j) = to.iter().position(|kind| kind.is_analogous_to(from[i])) {
+                result.flags |= COMPONENT_FLAGS[j];
+            }
+        }
+        result.flags |= self.flags & ColorFlags::ALPHA_IS_NONE;
+        result.flags |= result.powerless_components();
+
+        result
+    }
+
+    /// Convert the color to another color space and return its components,
+    /// with missing components treated as 0.
+    pub(crate) fn resolved_components_in(&self, color_space: ColorSpace) -> [f32; 3] {
+        self.to_color_space(color_space).resolved_components()
+    }
+
+    /// Flags for the components of the color that are powerless, meaning they
+    /// have no effect on the rendered color.
+    /// <https://drafts.csswg.org/css-color-4/#powerless>
+    fn powerless_components(&self) -> ColorFlags {
+        /// Tolerance for rounding errors picked up during conversion.
+        const EPSILON: f32 = 1.0e-6;
+
+        let Components(c0, c1, c2) = self.components;
+        let mut flags = ColorFlags::empty();
+
+        match self.color_space {
+            ColorSpace::Hsl => {
+                // Black and white have no saturation, which in turn means they
+                // have no hue.
+                let is_black_or_white = c2.abs() < EPSILON || (1.0 - c2).abs() < EPSILON;
+                if c0.is_nan() || c1.abs() < EPSILON || is_black_or_white {
+                    flags |= ColorFlags::C0_IS_NONE;
+                }
+                if is_black_or_white {
+                    flags |= ColorFlags::C1_IS_NONE;
+                }
+            }
+            ColorSpace::Hwb if c0.is_nan() || c1 + c2 >= 1.0 - EPSILON => {
+                flags |= ColorFlags::C0_IS_NONE;
+            }
+            ColorSpace::Lch | ColorSpace::Oklch if c2.is_nan() => {
+                flags |= ColorFlags::C2_IS_NONE;
+            }
+            _ => {}
+        }
+
+        flags
+    }
+
+    fn convert_components(
+        &self,
+        color_space: ColorSpace,
+        rgb_transform: Option<&Transform>,
+    ) -> Color {
+        use ColorSpace as C;
+
         // Handle conversions that can be done directly.
         match (self.color_space, color_space) {
             (C::Srgb, C::Hsl) => {
@@ -41,14 +397,49 @@ impl Color {
                 return Self::new(color_space, red, green, blue, self.alpha);
             }
 
+            // Both are sRGB with a hue, so they can skip the linear-light
+            // round trip.
+            (C::Hsl, C::Hwb) => {
+                let Components(hue, whiteness, blackness) = util::hsl_to_hwb(&self.components);
+                return Self::new(color_space, hue, whiteness, blackness, self.alpha);
+            }
+            (C::Hwb, C::Hsl) => {
+                let Components(hue, saturation, lightness) = util::hwb_to_hsl(&self.components);
+                return Self::new(color_space, hue, saturation, lightness, self.alpha);
+            }
+
+            // Oklab is defined relative to D65, like sRGB, so the round trip
+            // through XYZ-D50 can be skipped.
+            (C::Srgb, C::Oklab) => {
+                return self
+                    .as_model::<Srgb>()
+                    .to_linear_light()
+                    .to_oklab()
+                    .into_color(self.alpha);
+            }
+            (C::Srgb, C::Oklch) => {
+                return self
+                    .as_model::<Srgb>()
+                    .to_linear_light()
+                    .to_oklab()
+                    .to_oklch()
+                    .into_color(self.alpha);
+            }
+
             (C::Lch, C::Lab) | (C::Oklch, C::Oklab) => {
                 let Components(lightness, chroma, hue) =
                     util::polar_to_orthogonal(&self.components);
                 return Self::new(color_space, lightness, chroma, hue, self.alpha);
             }
-            (C::Lab, C::Lch) | (C::Oklab, C::Oklch) => {
-                let Components(lightness, a, b) = util::orthogonal_to_polar(&self.components);
-                return Self::new(color_space, lightness, a, b, self.alpha);
+            (C::Lab, C::Lch) => {
+                let Components(lightness, chroma, hue) =
+                    util::orthogonal_to_polar(&self.components, Lab::ACHROMATIC_EPSILON);
+                return Self::new(color_space, lightness, chroma, hue, self.alpha);
+            }
+            (C::Oklab, C::Oklch) => {
+                let Components(lightness, chroma, hue) =
+                    util::orthogonal_to_polar(&self.components, Oklab::ACHROMATIC_EPSILON);
+                return Self::new(color_space, lightness, chroma, hue, self.alpha);
             }
 
             _ => {
@@ -56,6 +447,11 @@ impl Color {
             }
         }
 
+        // RGB color spaces with the same white point can skip XYZ.
+        if let Some(rgb_transform) = rgb_transform {
+            return self.convert_linear_rgb(color_space, rgb_transform);
+        }
+
         // We have to go all the way to XYZ space to convert.
         let xyz = match self.color_space {
             C::Srgb => self
@@ -77,13 +473,33 @@ impl Color {
                 .to_xyz_d50(),
             C::Lab => self.as_model::<Lab>().to_xyz_d50(),
             C::Lch => self.as_model::<Lch>().to_lab().to_xyz_d50(),
-            C::Oklab => todo!(),
-            C::Oklch => todo!(),
+            C::Oklab => self.as_model::<Oklab>().to_xyz_d65().to_xyz_d50(),
+            C::Oklch => self
+                .as_model::<Oklch>()
+                .to_oklab()
+                .to_xyz_d65()
+                .to_xyz_d50(),
             C::SrgbLinear => self.as_model::<SrgbLinear>().to_xyz_d65().to_xyz_d50(),
-            C::DisplayP3 => todo!(),
-            C::A98Rgb => todo!(),
-            C::ProphotoRgb => todo!(),
-            C::Rec2020 => todo!(),
+            C::DisplayP3 => self
+                .as_model::<DisplayP3>()
+                .to_linear_light()
+                .to_xyz_d65()
+                .to_xyz_d50(),
+            C::A98Rgb => self
+                .as_model::<A98Rgb>()
+                .to_linear_light()
+                .to_xyz_d65()
+                .to_xyz_d50(),
+            // ProPhoto RGB is D50 referenced, so no chromatic adaptation needed.
+            C::ProphotoRgb => self
+                .as_model::<ProphotoRgb>()
+                .to_linear_light()
+                .to_xyz_d50(),
+            C::Rec2020 => self
+                .as_model::<Rec2020>()
+                .to_linear_light()
+                .to_xyz_d65()
+                .to_xyz_d50(),
             C::XyzD50 => XyzD50::new(
                 self.components.0,
                 self.components.1,
@@ -93,7 +509,7 @@ impl Color {
             C::XyzD65 => self.as_model::<XyzD65>().to_xyz_d50(),
         };
 
-        let _result: Color = match color_space {
+        let result: Color = match color_space {
             C::Srgb => xyz
                 .to_xyz_d65()
                 .to_srgb()
@@ -113,72 +529,394 @@ impl Color {
                 .into_color(self.alpha),
             C::Lab => xyz.to_lab().into_color(self.alpha),
             C::Lch => xyz.to_lab().to_lch().into_color(self.alpha),
-            C::Oklab => todo!(),
-            C::Oklch => todo!(),
+            C::Oklab => xyz.to_xyz_d65().to_oklab().into_color(self.alpha),
+            C::Oklch => xyz
+                .to_xyz_d65()
+                .to_oklab()
+                .to_oklch()
+                .into_color(self.alpha),
             C::SrgbLinear => xyz.to_xyz_d65().to_srgb().into_color(self.alpha),
-            C::DisplayP3 => todo!(),
-            C::A98Rgb => todo!(),
-            C::ProphotoRgb => todo!(),
-            C::Rec2020 => todo!(),
+            C::DisplayP3 => xyz
+                .to_xyz_d65()
+                .to_display_p3()
+                .to_gamma_encoded()
+                .into_color(self.alpha),
+            C::A98Rgb => xyz
+                .to_xyz_d65()
+                .to_a98_rgb()
+                .to_gamma_encoded()
+                .into_color(self.alpha),
+            C::ProphotoRgb => xyz
+                .to_prophoto_rgb()
+                .to_gamma_encoded()
+                .into_color(self.alpha),
+            C::Rec2020 => xyz
+                .to_xyz_d65()
+                .to_rec2020()
+                .to_gamma_encoded()
+                .into_color(self.alpha),
             C::XyzD50 => xyz.into_color(self.alpha),
             C::XyzD65 => xyz.to_xyz_d65().into_color(self.alpha),
         };
 
-        todo!()
+        result
     }
-}
 
-impl Srgb {
-    fn to_linear_light(&self) -> SrgbLinear {
-        let [red, green, blue] = [self.red, self.green, self.blue].map(|c| {
-            let abs = c.abs();
+    /// Convert between two RGB color spaces by applying `rgb_transform` to the
+    /// linear light components.
+    fn convert_linear_rgb(&self, color_space: ColorSpace, rgb_transform: &Transform) -> Color {
+        use ColorSpace as C;
+
+        let linear = match self.color_space {
+            C::Srgb => {
+                let linear = self.as_model::<Srgb>().to_linear_light();
+                Components(linear.red, linear.green, linear.blue)
+            }
+            C::SrgbLinear => self.components.clone(),
+            C::DisplayP3 => {
+                let linear = self.as_model::<DisplayP3>().to_linear_light();
+                Components(linear.red, linear.green, linear.blue)
+            }
+            C::A98Rgb => {
+                let linear = self.as_model::<A98Rgb>().to_linear_light();
+                Components(linear.red, linear.green, linear.blue)
+            }
+            C::Rec2020 => {
+                let linear = self.as_model::<Rec2020>().to_linear_light();
+                Components(linear.red, linear.green, linear.blue)
+            }
+            _ => unreachable!("Not an RGB color space with a D65 white point"),
+        };
+
+        let Components(red, green, blue) = transform(&linear, rgb_transform);
+
+        match color_space {
+            C::Srgb => SrgbLinear::new(red, green, blue, self.flags)
+                .to_gamma_encoded()
+                .into_color(self.alpha),
+            C::SrgbLinear => SrgbLinear::new(red, green, blue, self.flags).into_color(self.alpha),
+            C::DisplayP3 => DisplayP3Linear::new(red, green, blue, self.flags)
+                .to_gamma_encoded()
+                .into_color(self.alpha),
+            C::A98Rgb => A98RgbLinear::new(red, green, blue, self.flags)
+                .to_gamma_encoded()
+                .into_color(self.alpha),
+            C::Rec2020 => Rec2020Linear::new(red, green, blue, self.flags)
+                .to_gamma_encoded()
+                .into_color(self.alpha),
+            _ => unreachable!("Not an RGB color space with a D65 white point"),
+        }
+    }
+
+    /// The CIE Lab lightness of the color, from 0 to 100. A missing lightness
+    /// is treated as 0.
+    pub fn lightness(&self) -> f32 {
+        self.resolved_components_in(ColorSpace::Lab)[0]
+    }
+
+    /// The CIE LCH chroma of the color. A missing chroma is treated as 0.
+    pub fn chroma(&self) -> f32 {
+        self.resolved_components_in(ColorSpace::Lch)[1]
+    }
+
+    /// The CIE LCH hue of the color in degrees, or NaN if the color is
+    /// achromatic and the hue is powerless or missing.
+    pub fn hue(&self) -> f32 {
+        let lch = self.to_color_space(ColorSpace::Lch);
+        if lch.flags.contains(ColorFlags::C2_IS_NONE) {
+            f32::NAN
+        } else {
+            lch.components.2
+        }
+    }
+
+    /// The Oklch `(lightness, chroma, hue)` of the color, for sorting
+    /// palettes. A powerless or missing hue is -1, so achromatic colors sort
+    /// before the others with the same lightness and chroma, and other missing
+    /// components are 0. The values are never NaN for colors with finite
+    /// components, so the keys can be compared with `partial_cmp`.
+    pub fn oklch_sort_key(&self) -> (f32, f32, f32) {
+        let oklch = self.to_color_space(ColorSpace::Oklch);
+        let [lightness, chroma, hue] = oklch.resolved_components();
+        let hue = if oklch.flags.contains(ColorFlags::C2_IS_NONE) || hue.is_nan() {
+            -1.0
+        } else {
+            hue
+        };
+        (lightness, chroma, hue)
+    }
+
+    /// Convert the color to sRGB, the color space most output expects.
+    pub fn to_srgb(&self) -> Color {
+        self.to_color_space(ColorSpace::Srgb)
+    }
+
+    /// Convert the color to sRGB and return its red, green, blue and alpha,
+    /// with missing components treated as 0. The channels are not clamped.
+    pub fn to_srgb_tuple(&self) -> (f32, f32, f32, f32) {
+        let srgb = self.to_srgb();
+        let [red, green, blue] = srgb.resolved_components();
+        (red, green, blue, srgb.resolved_alpha())
+    }
+
+    /// Convert the color to XYZ relative to the D50 white point, the reference
+    /// white of Lab and ProPhoto RGB, which convert to it without chromatic
+    /// adaptation.
+    pub fn to_xyz_d50(&self) -> Color {
+        self.to_color_space(ColorSpace::XyzD50)
+    }
+
+    /// Convert the color to XYZ relative to the D65 white point, the reference
+    /// white of sRGB and most other RGB color spaces.
+    pub fn to_xyz_d65(&self) -> Color {
+        self.to_color_space(ColorSpace::XyzD65)
+    }
+
+    /// Convert the color to linear-light sRGB, the space compositing and
+    /// blending are usually done in.
+    pub fn to_linear_srgb(&self) -> Color {
+        self.to_color_space(ColorSpace::SrgbLinear)
+    }
 
-            if abs < 0.04045 {
-                c / 12.92
+    /// Convert the color to sRGB and quantize it to 8-bit channels. Channels
+    /// outside the sRGB gamut are clamped and missing components are treated
+    /// as 0.
+    pub fn to_rgba8(&self) -> [u8; 4] {
+        let srgb = self.to_color_space(ColorSpace::Srgb);
+
+        [
+            (srgb.components.0, ColorFlags::C0_IS_NONE),
+            (srgb.components.1, ColorFlags::C1_IS_NONE),
+            (srgb.components.2, ColorFlags::C2_IS_NONE),
+            (srgb.alpha, ColorFlags::ALPHA_IS_NONE),
+        ]
+        .map(|(value, flag)| {
+            if srgb.flags.contains(flag) || value.is_nan() {
+                0
             } else {
-                c.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+                (value.clamp(0.0, 1.0) * 255.0).round() as u8
             }
-        });
+        })
+    }
+
+    /// Create an sRGB color from 8-bit channels.
+    pub fn from_rgba8([red, green, blue, alpha]: [u8; 4]) -> Color {
+        Color::new(
+            ColorSpace::Srgb,
+            red as f32 / 255.0,
+            green as f32 / 255.0,
+            blue as f32 / 255.0,
+            alpha as f32 / 255.0,
+        )
+    }
+
+    /// Convert each of the colors to the given color space.
+    pub fn convert_slice(colors: &[Color], dest: ColorSpace) -> Vec<Color> {
+        let mut result = colors.to_vec();
+        Self::convert_slice_mut(&mut result, dest);
+        result
+    }
+
+    /// Convert each of the colors to the given color space in place. If all
+    /// the colors are in the same RGB color space, the matrix for the
+    /// conversion is only composed once.
+    pub fn convert_slice_mut(colors: &mut [Color], dest: ColorSpace) {
+        let Some(first) = colors.first() else {
+            return;
+        };
+
+        let source = first.color_space;
+        if colors.iter().all(|color| color.color_space == source) {
+            let rgb_transform = linear_rgb_transform(source, dest);
+            for color in colors.iter_mut() {
+                *color = color.to_color_space_with(dest, rgb_transform.as_ref());
+            }
+        } else {
+            for color in colors.iter_mut() {
+                *color = color.to_color_space(dest);
+            }
+        }
+    }
+}
+
+/// An opaque sRGB color from 8-bit red, green and blue channels.
+impl From<[u8; 3]> for Color {
+    fn from([red, green, blue]: [u8; 3]) -> Self {
+        Color::from_rgba8([red, green, blue, 255])
+    }
+}
+
+/// An sRGB color from 8-bit red, green, blue and alpha channels, the same as
+/// [`Color::from_rgba8`].
+impl From<[u8; 4]> for Color {
+    fn from(rgba: [u8; 4]) -> Self {
+        Color::from_rgba8(rgba)
+    }
+}
+
+impl Srgb {
+    fn to_linear_light(&self) -> SrgbLinear {
+        let [red, green, blue] = [self.red, self.green, self.blue].map(util::srgb_to_linear);
 
         SrgbLinear::new(red, green, blue, self.flags)
     }
 
     fn to_hsl(&self) -> Hsl {
-        let Components(hue, saturation, lightness) = util::rgb_to_hsl(self.components());
+        let Components(hue, saturation, lightness) = util::rgb_to_hsl(&self.components());
         Hsl::new(hue, saturation, lightness, self.flags)
     }
 
     fn to_hwb(&self) -> Hwb {
-        let Components(hue, whiteness, blackness) = util::rgb_to_hwb(self.components());
+        let Components(hue, whiteness, blackness) = util::rgb_to_hwb(&self.components());
         Hwb::new(hue, whiteness, blackness, self.flags)
     }
 }
 
 impl SrgbLinear {
     pub fn to_gamma_encoded(&self) -> Srgb {
-        let Components(red, green, blue) = self.components().map(|c| {
+        let Components(red, green, blue) = self.components().map(util::linear_to_srgb);
+
+        Srgb::new(red, green, blue, self.flags)
+    }
+
+    pub fn to_xyz_d65(&self) -> XyzD65 {
+        let Components(x, y, z) = transform(&self.components(), &SRGB_TO_XYZ_D65);
+
+        XyzD65::new(x, y, z, self.flags)
+    }
+}
+
+impl DisplayP3 {
+    /// Display P3 shares the transfer function of sRGB.
+    fn to_linear_light(&self) -> DisplayP3Linear {
+        let [red, green, blue] = [self.red, self.green, self.blue].map(util::srgb_to_linear);
+
+        DisplayP3Linear::new(red, green, blue, self.flags)
+    }
+}
+
+impl DisplayP3Linear {
+    pub fn to_gamma_encoded(&self) -> DisplayP3 {
+        let [red, green, blue] = [self.red, self.green, self.blue].map(util::linear_to_srgb);
+
+        DisplayP3::new(red, green, blue, self.flags)
+    }
+
+    pub fn to_xyz_d65(&self) -> XyzD65 {
+        let components = Components(self.red, self.green, self.blue);
+        let Components(x, y, z) = transform(&components, &DISPLAY_P3_TO_XYZ_D65);
+
+        XyzD65::new(x, y, z, self.flags)
+    }
+}
+
+impl A98Rgb {
+    const GAMMA: f32 = 563.0 / 256.0;
+
+    fn to_linear_light(&self) -> A98RgbLinear {
+        let [red, green, blue] =
+            [self.red, self.green, self.blue].map(|c| c.signum() * c.abs().powf(Self::GAMMA));
+
+        A98RgbLinear::new(red, green, blue, self.flags)
+    }
+}
+
+impl A98RgbLinear {
+    pub fn to_gamma_encoded(&self) -> A98Rgb {
+        let [red, green, blue] = [self.red, self.green, self.blue]
+            .map(|c| c.signum() * c.abs().powf(1.0 / A98Rgb::GAMMA));
+
+        A98Rgb::new(red, green, blue, self.flags)
+    }
+
+    pub fn to_xyz_d65(&self) -> XyzD65 {
+        let components = Components(self.red, self.green, self.blue);
+        let Components(x, y, z) = transform(&components, &A98_RGB_TO_XYZ_D65);
+
+        XyzD65::new(x, y, z, self.flags)
+    }
+}
+
+impl ProphotoRgb {
+    fn to_linear_light(&self) -> ProphotoRgbLinear {
+        const ET2: f32 = 16.0 / 512.0;
+
+        let [red, green, blue] = [self.red, self.green, self.blue].map(|c| {
             let abs = c.abs();
 
-            if abs > 0.0031308 {
-                c.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+            if abs <= ET2 {
+                c / 16.0
             } else {
-                12.92 * c
+                c.signum() * abs.powf(1.8)
             }
         });
 
-        Srgb::new(red, green, blue, self.flags)
+        ProphotoRgbLinear::new(red, green, blue, self.flags)
     }
+}
 
-    pub fn to_xyz_d65(&self) -> XyzD65 {
-        #[rustfmt::skip]
-        const TO_XYZ: Transform = Transform::new(
-            0.4123907992659595,  0.21263900587151036, 0.01933081871559185, 0.0,
-            0.35758433938387796, 0.7151686787677559,  0.11919477979462599, 0.0,
-            0.1804807884018343,  0.07219231536073371, 0.9505321522496606,  0.0,
-            0.0,                 0.0,                 0.0,                 1.0,
-        );
+impl ProphotoRgbLinear {
+    pub fn to_gamma_encoded(&self) -> ProphotoRgb {
+        const ET: f32 = 1.0 / 512.0;
+
+        let [red, green, blue] = [self.red, self.green, self.blue].map(|c| {
+            let abs = c.abs();
+
+            if abs >= ET {
+                c.signum() * abs.powf(1.0 / 1.8)
+            } else {
+                16.0 * c
+            }
+        });
+
+        ProphotoRgb::new(red, green, blue, self.flags)
+    }
+
+    pub fn to_xyz_d50(&self) -> XyzD50 {
+        let components = Components(self.red, self.green, self.blue);
+        let Components(x, y, z) = transform(&components, &PROPHOTO_RGB_TO_XYZ_D50);
 
-        let Components(x, y, z) = transform(self.components(), &TO_XYZ);
+        XyzD50::new(x, y, z, self.flags)
+    }
+}
+
+impl Rec2020 {
+    const ALPHA: f32 = 1.09929682680944;
+    const BETA: f32 = 0.018053968510807;
+
+    fn to_linear_light(&self) -> Rec2020Linear {
+        let [red, green, blue] = [self.red, self.green, self.blue].map(|c| {
+            let abs = c.abs();
+
+            if abs < Self::BETA * 4.5 {
+                c / 4.5
+            } else {
+                c.signum() * ((abs + Self::ALPHA - 1.0) / Self::ALPHA).powf(1.0 / 0.45)
+            }
+        });
+
+        Rec2020Linear::new(red, green, blue, self.flags)
+    }
+}
+
+impl Rec2020Linear {
+    pub fn to_gamma_encoded(&self) -> Rec2020 {
+        let [red, green, blue] = [self.red, self.green, self.blue].map(|c| {
+            let abs = c.abs();
+
+            if abs > Rec2020::BETA {
+                c.signum() * (Rec2020::ALPHA * abs.powf(0.45) - (Rec2020::ALPHA - 1.0))
+            } else {
+                4.5 * c
+            }
+        });
+
+        Rec2020::new(red, green, blue, self.flags)
+    }
+
+    pub fn to_xyz_d65(&self) -> XyzD65 {
+        let components = Components(self.red, self.green, self.blue);
+        let Components(x, y, z) = transform(&components, &REC2020_TO_XYZ_D65);
 
         XyzD65::new(x, y, z, self.flags)
     }
@@ -186,14 +924,14 @@ impl SrgbLinear {
 
 impl Hsl {
     pub fn to_srgb(&self) -> Srgb {
-        let Components(red, green, blue) = util::hsl_to_rgb(self.components());
+        let Components(red, green, blue) = util::hsl_to_rgb(&self.components());
         Srgb::new(red, green, blue, self.flags)
     }
 }
 
 impl Hwb {
     pub fn to_srgb(&self) -> Srgb {
-        let Components(red, green, blue) = util::hwb_to_rgb(self.components());
+        let Components(red, green, blue) = util::hwb_to_rgb(&self.components());
         Srgb::new(red, green, blue, self.flags)
     }
 }
@@ -202,7 +940,17 @@ impl Lab {
     const KAPPA: f32 = 24389.0 / 27.0;
     const EPSILON: f32 = 216.0 / 24389.0;
 
+    /// Chroma at or below which the hue is considered powerless.
+    const ACHROMATIC_EPSILON: f32 = 0.0015;
+
+    /// Convert to XYZ, relative to the D50 white point that CSS uses for Lab.
     pub fn to_xyz_d50(&self) -> XyzD50 {
+        self.to_xyz::<D50>()
+    }
+
+    /// Convert to XYZ, interpreting the color as Lab relative to the white
+    /// point `W`.
+    pub fn to_xyz<W: WhitePoint>(&self) -> Xyz<W> {
         let f1 = (self.lightness + 16.0) / 116.0;
         let f0 = f1 + self.a / 500.0;
         let f2 = f1 - self.b / 200.0;
@@ -228,58 +976,92 @@ impl Lab {
             (116.0 * f2 - 16.0) / Self::KAPPA
         };
 
-        XyzD50::new(
-            x * D50::WHITE_POINT.0,
-            y * D50::WHITE_POINT.1,
-            z * D50::WHITE_POINT.2,
+        Xyz::new(
+            x * W::WHITE_POINT.0,
+            y * W::WHITE_POINT.1,
+            z * W::WHITE_POINT.2,
             self.flags,
         )
     }
 
     pub fn to_lch(&self) -> Lch {
-        let Components(lightness, chroma, hue) = util::orthogonal_to_polar(self.components());
+        let Components(lightness, chroma, hue) =
+            util::orthogonal_to_polar(&self.components(), Self::ACHROMATIC_EPSILON);
         Lch::new(lightness, chroma, hue, self.flags)
     }
 }
 
 impl Lch {
     pub fn to_lab(&self) -> Lab {
-        let Components(lightness, a, b) = util::polar_to_orthogonal(self.components());
+        let Components(lightness, a, b) = util::polar_to_orthogonal(&self.components());
         Lab::new(lightness, a, b, self.flags)
     }
 }
 
-impl XyzD50 {
-    pub fn to_xyz_d65(&self) -> XyzD65 {
-        #[rustfmt::skip]
-        const MAT: Transform = Transform::new(
-             0.9554734527042182,   -0.028369706963208136,  0.012314001688319899, 0.0,
-            -0.023098536874261423,  1.0099954580058226,   -0.020507696433477912, 0.0,
-             0.0632593086610217,    0.021041398966943008,  1.3303659366080753,   0.0,
-             0.0,                   0.0,                   0.0,                  1.0,
-        );
+impl SrgbLinear {
+    pub fn to_oklab(&self) -> Oklab {
+        let lms = transform(&self.components(), &SRGB_TO_LMS).map(f32::cbrt);
+        let Components(lightness, a, b) = transform(&lms, &LMS_TO_OKLAB);
+
+        Oklab::new(lightness, a, b, self.flags)
+    }
+}
+
+impl Oklab {
+    /// Chroma at or below which the hue is considered powerless.
+    const ACHROMATIC_EPSILON: f32 = 0.000004;
+
+    pub fn to_xyz_d65(&self) -> XyzD65 {
+        // Undo the non-linearity by cubing the cone responses.
+        let lms = transform(&self.components(), &OKLAB_TO_LMS).map(|v| v * v * v);
+        let Components(x, y, z) = transform(&lms, &LMS_TO_XYZ);
+
+        XyzD65::new(x, y, z, self.flags)
+    }
+
+    pub fn to_oklch(&self) -> Oklch {
+        let Components(lightness, chroma, hue) =
+            util::orthogonal_to_polar(&self.components(), Self::ACHROMATIC_EPSILON);
+        Oklch::new(lightness, chroma, hue, self.flags)
+    }
+}
+
+impl Oklch {
+    pub fn to_oklab(&self) -> Oklab {
+        let Components(lightness, a, b) = util::polar_to_orthogonal(&self.components());
+        Oklab::new(lightness, a, b, self.flags)
+    }
+}
 
-        let Components(x, y, z) = transform(self.components(), &MAT);
+impl XyzD50 {
+    pub fn to_xyz_d65(&self) -> XyzD65 {
+        let Components(x, y, z) = chromatic_adapt::<D50, D65>(&self.components());
 
         XyzD65::new(x, y, z, self.flags)
     }
 
-    fn to_lab(&self) -> Lab {
-        const KAPPA: f32 = 24389.0 / 27.0;
-        const EPSILON: f32 = 216.0 / 24389.0;
+    pub fn to_prophoto_rgb(&self) -> ProphotoRgbLinear {
+        let Components(red, green, blue) = transform(&self.components(), &XYZ_D50_TO_PROPHOTO_RGB);
 
+        ProphotoRgbLinear::new(red, green, blue, self.flags)
+    }
+}
+
+impl<W: WhitePoint> Xyz<W> {
+    /// Convert to Lab relative to the white point `W`. CSS Lab is relative to
+    /// D50, so only the result for [`XyzD50`] can be stored in a [`Color`].
+    pub fn to_lab(&self) -> Lab {
         let adapted = [
-            self.x / D50::WHITE_POINT.0,
-            self.y / D50::WHITE_POINT.1,
-            self.z / D50::WHITE_POINT.2,
+            self.x / W::WHITE_POINT.0,
+            self.y / W::WHITE_POINT.1,
+            self.z / W::WHITE_POINT.2,
         ];
 
-        // 4. Convert D50-adapted XYZ to Lab.
         let [f0, f1, f2] = adapted.map(|v| {
-            if v > EPSILON {
+            if v > Lab::EPSILON {
                 v.cbrt()
             } else {
-                (KAPPA * v + 16.0) / 116.0
+                (Lab::KAPPA * v + 16.0) / 116.0
             }
         });
 
@@ -293,40 +1075,72 @@ impl XyzD50 {
 
 impl XyzD65 {
     pub fn to_srgb(&self) -> SrgbLinear {
-        #[rustfmt::skip]
-        const FROM_XYZ: Transform = Transform::new(
-             3.2409699419045213, -0.9692436362808798,  0.05563007969699361, 0.0,
-            -1.5373831775700935,  1.8759675015077206, -0.20397695888897657, 0.0,
-            -0.4986107602930033,  0.04155505740717561, 1.0569715142428786,  0.0,
-             0.0,                 0.0,                 0.0,                 1.0,
-        );
-
-        let Components(red, green, blue) = transform(self.components(), &FROM_XYZ);
+        let Components(red, green, blue) = transform(&self.components(), &XYZ_D65_TO_SRGB);
 
         SrgbLinear::new(red, green, blue, self.flags)
     }
 
-    pub fn to_xyz_d50(&self) -> XyzD50 {
-        #[rustfmt::skip]
-        const MAT: Transform = Transform::new(
-             1.0479298208405488,    0.029627815688159344, -0.009243058152591178, 0.0,
-             0.022946793341019088,  0.990434484573249,     0.015055144896577895, 0.0,
-            -0.05019222954313557,  -0.01707382502938514,   0.7518742899580008,   0.0,
-             0.0,                   0.0,                   0.0,                  1.0,
-        );
+    pub fn to_display_p3(&self) -> DisplayP3Linear {
+        let Components(red, green, blue) = transform(&self.components(), &XYZ_D65_TO_DISPLAY_P3);
 
-        let Components(x, y, z) = transform(self.components(), &MAT);
+        DisplayP3Linear::new(red, green, blue, self.flags)
+    }
+
+    pub fn to_a98_rgb(&self) -> A98RgbLinear {
+        let Components(red, green, blue) = transform(&self.components(), &XYZ_D65_TO_A98_RGB);
+
+        A98RgbLinear::new(red, green, blue, self.flags)
+    }
+
+    pub fn to_rec2020(&self) -> Rec2020Linear {
+        let Components(red, green, blue) = transform(&self.components(), &XYZ_D65_TO_REC2020);
+
+        Rec2020Linear::new(red, green, blue, self.flags)
+    }
+
+    pub fn to_xyz_d50(&self) -> XyzD50 {
+        let Components(x, y, z) = chromatic_adapt::<D65, D50>(&self.components());
 
         XyzD50::new(x, y, z, self.flags)
     }
+
+    pub fn to_oklab(&self) -> Oklab {
+        // Apply the non-linearity to the cone responses.
+        let lms = transform(&self.components(), &XYZ_TO_LMS).map(f32::cbrt);
+        let Components(lightness, a, b) = transform(&lms, &LMS_TO_OKLAB);
+
+        Oklab::new(lightness, a, b, self.flags)
+    }
 }
 
 mod util {
     use super::super::color::Components;
+    #[cfg(not(any(feature = "std", test)))]
+    use crate::math::Float;
+    use crate::model::wrap_hue;
+
+    /// Convert a gamma encoded sRGB channel to linear light.
+    /// <https://drafts.csswg.org/css-color-4/#color-conversion-code>
+    pub fn srgb_to_linear(c: f32) -> f32 {
+        let abs = c.abs();
 
-    /// Normalize hue into [0, 360).
-    fn normalize_hue(hue: f32) -> f32 {
-        hue.rem_euclid(360.0)
+        if abs < 0.04045 {
+            c / 12.92
+        } else {
+            c.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    /// Convert a linear light sRGB channel to gamma encoded form.
+    /// <https://drafts.csswg.org/css-color-4/#color-conversion-code>
+    pub fn linear_to_srgb(c: f32) -> f32 {
+        let abs = c.abs();
+
+        if abs > 0.0031308 {
+            c.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+        } else {
+            12.92 * c
+        }
     }
 
     /// Calculate the hue from RGB components and return it along with the min and
@@ -375,11 +1189,21 @@ mod util {
         Components(hue, saturation, lightness)
     }
 
-    /// Convert from HSL notation to RGB notation.
+    /// Resolve a NaN (powerless) hue to 0.
+    fn resolve_hue(hue: f32) -> f32 {
+        if hue.is_nan() {
+            0.0
+        } else {
+            hue
+        }
+    }
+
+    /// Convert from HSL notation to RGB notation. A NaN (powerless) hue is
+    /// treated as 0.
     /// https://drafts.csswg.org/css-color-4/#hsl-to-rgb
     pub fn hsl_to_rgb(from: &Components) -> Components {
         fn hue_to_rgb(t1: f32, t2: f32, hue: f32) -> f32 {
-            let hue = normalize_hue(hue);
+            let hue = wrap_hue(hue);
 
             if hue * 6.0 < 360.0 {
                 t1 + (t2 - t1) * hue / 60.0
@@ -393,6 +1217,7 @@ mod util {
         }
 
         let Components(hue, saturation, lightness) = *from;
+        let hue = resolve_hue(hue);
 
         let t2 = if lightness <= 0.5 {
             lightness * (saturation + 1.0)
@@ -421,7 +1246,8 @@ mod util {
         Components(hue, whiteness, blackness)
     }
 
-    /// Convert from HWB notation to RGB notation.
+    /// Convert from HWB notation to RGB notation. A NaN (powerless) hue is
+    /// treated as 0.
     /// https://drafts.csswg.org/css-color-4/#hwb-to-rgb
     pub fn hwb_to_rgb(from: &Components) -> Components {
         let Components(hue, whiteness, blackness) = *from;
@@ -435,13 +1261,40 @@ mod util {
         hsl_to_rgb(&Components(hue, 1.0, 0.5)).map(|v| v * x + whiteness)
     }
 
+    /// Convert from HSL notation to HWB notation through RGB, keeping the hue
+    /// of the HSL color unless the color is achromatic. A NaN (powerless) hue
+    /// is treated as 0, as in [`hsl_to_rgb`].
+    pub fn hsl_to_hwb(from: &Components) -> Components {
+        let Components(hue, whiteness, blackness) = rgb_to_hwb(&hsl_to_rgb(from));
+        let hue = if hue.is_nan() {
+            hue
+        } else {
+            wrap_hue(resolve_hue(from.0))
+        };
+        Components(hue, whiteness, blackness)
+    }
+
+    /// Convert from HWB notation to HSL notation through RGB, keeping the hue
+    /// of the HWB color unless the color is achromatic. A NaN (powerless) hue
+    /// is treated as 0, as in [`hwb_to_rgb`].
+    pub fn hwb_to_hsl(from: &Components) -> Components {
+        let Components(hue, saturation, lightness) = rgb_to_hsl(&hwb_to_rgb(from));
+        let hue = if hue.is_nan() {
+            hue
+        } else {
+            wrap_hue(resolve_hue(from.0))
+        };
+        Components(hue, saturation, lightness)
+    }
+
     /// Convert from a cylindrical polar coordinate to the rectangular orthogonal
-    /// form. This is used to convert (ok)lch to (ok)lab.
+    /// form. This is used to convert (ok)lch to (ok)lab. A NaN (powerless) hue
+    /// is treated as 0.
     /// <https://drafts.csswg.org/css-color-4/#color-conversion-code>
     pub fn polar_to_orthogonal(from: &Components) -> Components {
         let Components(lightness, chroma, hue) = *from;
 
-        let hue = hue.to_radians();
+        let hue = if hue.is_nan() { 0.0 } else { hue.to_radians() };
         let a = chroma * hue.cos();
         let b = chroma * hue.sin();
 
@@ -449,13 +1302,18 @@ mod util {
     }
 
     /// Convert from the rectangular orthogonal form to a cylindrical polar
-    /// coordinate. This is used to convert (ok)lab to (ok)lch.
+    /// coordinate. This is used to convert (ok)lab to (ok)lch. If the chroma is
+    /// at or below `epsilon` the color is achromatic and the hue is NaN.
     /// <https://drafts.csswg.org/css-color-4/#color-conversion-code>
-    pub fn orthogonal_to_polar(from: &Components) -> Components {
+    pub fn orthogonal_to_polar(from: &Components, epsilon: f32) -> Components {
         let Components(lightness, a, b) = *from;
 
-        let hue = b.atan2(a).to_degrees().rem_euclid(360.0);
         let chroma = (a * a + b * b).sqrt();
+        let hue = if chroma > epsilon {
+            wrap_hue(b.atan2(a).to_degrees())
+        } else {
+            f32::NAN
+        };
 
         Components(lightness, chroma, hue)
     }
@@ -464,13 +1322,7 @@ mod util {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    macro_rules! almost_equal {
-        ($c1:expr, $c2:expr) => {{
-            // Equality within 4 decimal places.
-            ($c2 - $c1).abs() < 1.0e-4
-        }};
-    }
+    use crate::test_utils::assert_color_close;
 
     #[test]
     fn no_conversion_happens_if_color_spaces_are_equal() {
@@ -487,6 +1339,23 @@ mod tests {
 
             (ColorSpace::Lab, 56.6293, 39.2371, 57.5538, 1.0, ColorSpace::Lch, 56.6293, 69.6562, 55.7159, 1.0),
             (ColorSpace::Lch, 56.6293, 69.6562, 55.7159, 1.0, ColorSpace::Lab, 56.6293, 39.2371, 57.5538, 1.0),
+
+            (ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0, ColorSpace::Oklab, 0.62796, 0.22486, 0.12585, 1.0),
+            (ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0, ColorSpace::Oklab, 0.6344, 0.09905, 0.1192, 1.0),
+
+            (ColorSpace::DisplayP3, 1.0, 0.0, 0.0, 1.0, ColorSpace::XyzD65, 0.48657, 0.22897, 0.0, 1.0),
+            (ColorSpace::DisplayP3, 1.0, 0.0, 0.0, 1.0, ColorSpace::Srgb, 1.09307, -0.22674, -0.15013, 1.0),
+            (ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0, ColorSpace::DisplayP3, 0.91749, 0.20029, 0.13856, 1.0),
+
+            (ColorSpace::A98Rgb, 1.0, 0.5, 0.0, 1.0, ColorSpace::Srgb, 1.12624, 0.50399, -0.09556, 1.0),
+            (ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0, ColorSpace::A98Rgb, 0.73039, 0.41072, 0.16198, 1.0),
+
+            (ColorSpace::ProphotoRgb, 1.0, 1.0, 1.0, 1.0, ColorSpace::XyzD50, 0.96430, 1.0, 0.82510, 1.0),
+            (ColorSpace::ProphotoRgb, 1.0, 1.0, 1.0, 1.0, ColorSpace::Lab, 100.0, 0.0, 0.0, 1.0),
+            (ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0, ColorSpace::ProphotoRgb, 0.59229, 0.39417, 0.16427, 1.0),
+
+            (ColorSpace::Rec2020, 0.5, 0.5, 0.5, 1.0, ColorSpace::Srgb, 0.54658, 0.54658, 0.54658, 1.0),
+            (ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0, ColorSpace::Rec2020, 0.66925, 0.40193, 0.14270, 1.0),
         ];
 
         for (
@@ -505,33 +1374,618 @@ mod tests {
             let from = Color::new(from_color_space, from_c0, from_c1, from_c2, from_alpha);
             let to = Color::new(to_color_space, to_c0, to_c1, to_c2, to_alpha);
 
-            let result = from.to_color_space(to_color_space);
-
-            assert_eq!(result.color_space, to.color_space);
-            assert!(
-                almost_equal!(result.components.0, to.components.0),
-                "c0 {} is not equal to {}",
-                result.components.0,
-                to.components.0
-            );
-            assert!(
-                almost_equal!(result.components.1, to.components.1),
-                "c1 {} is not equal to {}",
-                result.components.1,
-                to.components.1
-            );
-            assert!(
-                almost_equal!(result.components.2, to.components.2),
-                "c2 {} is not equal to {}",
-                result.components.2,
-                to.components.2
-            );
-            assert!(
-                almost_equal!(result.alpha, to.alpha),
-                "alpha {} is not equal to {}",
-                result.alpha,
-                to.alpha
-            );
+            assert_color_close(&from.to_color_space(to_color_space), &to, 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn oklab_reference_values() {
+        // Reference values from <https://bottosson.github.io/posts/oklab/>, which
+        // are only given to 3 decimal places.
+        #[rustfmt::skip]
+        let values = [
+            ((0.950, 1.000, 1.089), (1.000,  0.000,  0.000)),
+            ((1.000, 0.000, 0.000), (0.450,  1.236, -0.019)),
+            ((0.000, 1.000, 0.000), (0.922, -0.671,  0.263)),
+            ((0.000, 0.000, 1.000), (0.153, -1.415, -0.449)),
+        ];
+
+        for ((x, y, z), (lightness, a, b)) in values {
+            let oklab =
+                Color::new(ColorSpace::XyzD65, x, y, z, 1.0).to_color_space(ColorSpace::Oklab);
+
+            assert_eq!(oklab.color_space, ColorSpace::Oklab);
+            assert!((oklab.components.0 - lightness).abs() < 1.0e-3);
+            assert!((oklab.components.1 - a).abs() < 1.0e-3);
+            assert!((oklab.components.2 - b).abs() < 1.0e-3);
+        }
+    }
+
+    #[test]
+    fn round_trip_between_every_pair_of_color_spaces() {
+        let seed = Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 0.5);
+
+        for &source in ColorSpace::all() {
+            for &dest in ColorSpace::all() {
+                let result = seed
+                    .to_color_space(source)
+                    .to_color_space(dest)
+                    .to_color_space(source)
+                    .to_color_space(ColorSpace::Srgb);
+
+                assert!(
+                    result.approx_eq(&seed, 1.0e-3),
+                    "{source:?} -> {dest:?}: {result:?} is not equal to {seed:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hsl_hwb_direct_path_matches_conversion_through_xyz() {
+        for (from, to) in [
+            (
+                Color::new(ColorSpace::Hsl, 25.0, 0.75, 0.47, 0.5),
+                ColorSpace::Hwb,
+            ),
+            (
+                Color::new(ColorSpace::Hsl, 200.0, 0.3, 0.8, 1.0),
+                ColorSpace::Hwb,
+            ),
+            (
+                Color::new(ColorSpace::Hwb, 120.0, 0.2, 0.3, 1.0),
+                ColorSpace::Hsl,
+            ),
+            (
+                Color::new(ColorSpace::Hwb, 300.0, 0.6, 0.1, 0.25),
+                ColorSpace::Hsl,
+            ),
+            // An unflagged NaN hue of a chromatic color is treated as 0.
+            (
+                Color::new(ColorSpace::Hsl, f32::NAN, 0.5, 0.5, 1.0),
+                ColorSpace::Hwb,
+            ),
+            (
+                Color::new(ColorSpace::Hwb, f32::NAN, 0.2, 0.3, 1.0),
+                ColorSpace::Hsl,
+            ),
+        ] {
+            let direct = from.to_color_space(to);
+            let mut through_xyz = from.to_color_space(ColorSpace::XyzD65).to_color_space(to);
+            // Rounding through XYZ can put a hue of 0 just below 360.
+            if through_xyz.components.0 > 359.99 {
+                through_xyz.components.0 -= 360.0;
+            }
+            assert_color_close(&direct, &through_xyz, 1.0e-4);
+            let hue = if from.components.0.is_nan() {
+                0.0
+            } else {
+                from.components.0
+            };
+            assert_eq!(direct.components.0, hue);
+        }
+    }
+
+    #[test]
+    fn nan_hue_is_treated_as_zero() {
+        let gray = Components(0.5, 0.5, 0.5);
+        assert_eq!(util::hsl_to_rgb(&Components(f32::NAN, 0.0, 0.5)), gray);
+        assert_eq!(util::hwb_to_rgb(&Components(f32::NAN, 0.5, 0.5)), gray);
+
+        let srgb = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.5, 1.0);
+        for hsl in [
+            Color::parse("hsl(none 0% 50%)").unwrap(),
+            Color::new(ColorSpace::Hsl, f32::NAN, 0.0, 0.5, 1.0),
+        ] {
+            assert_eq!(hsl.to_color_space(ColorSpace::Srgb), srgb);
+        }
+    }
+
+    #[test]
+    fn srgb_to_oklab_round_trip() {
+        let from = Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0);
+        let to = from
+            .to_color_space(ColorSpace::Oklab)
+            .to_color_space(ColorSpace::Srgb);
+
+        assert_color_close(&to, &from, 1.0e-4);
+    }
+
+    #[test]
+    fn oklch_to_srgb_round_trip() {
+        let from = Color::new(ColorSpace::Oklch, 0.7, 0.15, 30.0, 1.0);
+
+        let srgb = from.to_color_space(ColorSpace::Srgb);
+        assert_color_close(
+            &srgb,
+            &Color::new(ColorSpace::Srgb, 0.9286, 0.4632, 0.3943, 1.0),
+            1.0e-4,
+        );
+
+        let to = srgb.to_color_space(ColorSpace::Oklch);
+        assert_color_close(&to, &from, 1.0e-4);
+    }
+
+    #[test]
+    fn achromatic_oklch_has_nan_hue() {
+        let gray = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.5, 1.0);
+        let oklch = gray.to_color_space(ColorSpace::Oklch);
+
+        assert!(oklch.components.1.abs() < 1.0e-4);
+        assert!(oklch.components.2.is_nan());
+
+        let back = oklch.to_color_space(ColorSpace::Srgb);
+        assert_color_close(&back, &gray, 1.0e-4);
+    }
+
+    #[test]
+    fn rec2020_round_trip() {
+        let from = Color::new(ColorSpace::Rec2020, 0.7, 0.4, 0.2, 1.0);
+        let to = from
+            .to_color_space(ColorSpace::Srgb)
+            .to_color_space(ColorSpace::Rec2020);
+
+        assert_color_close(&to, &from, 1.0e-4);
+    }
+
+    #[test]
+    fn srgb_to_xyz_d50_returns_a_result() {
+        let from = Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0);
+        let to = from.to_color_space(ColorSpace::XyzD50);
+
+        assert_color_close(
+            &to,
+            &Color::new(ColorSpace::XyzD50, 0.33729, 0.24546, 0.03195, 1.0),
+            1.0e-4,
+        );
+    }
+
+    #[test]
+    fn lightness_chroma_and_hue() {
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+        assert!((blue.lightness() - 29.568).abs() < 1.0e-2);
+        assert!((blue.chroma() - 131.20).abs() < 1.0e-1);
+        assert!((blue.hue() - 301.36).abs() < 1.0e-1);
+
+        // The same blue has a hue near 264 degrees in OKLCH.
+        let oklch = blue.to_color_space(ColorSpace::Oklch);
+        assert!((oklch.components.2 - 264.05).abs() < 1.0e-1);
+
+        let gray = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.5, 1.0);
+        assert!(gray.chroma() < 1.0e-2);
+        assert!(gray.hue().is_nan());
+        assert!(Color::new(ColorSpace::Lch, 50.0, 0.0, None, 1.0)
+            .hue()
+            .is_nan());
+    }
+
+    #[test]
+    fn oklch_sort_key() {
+        let mut colors = [
+            Color::new(ColorSpace::Oklch, 0.8, 0.1, 200.0, 1.0),
+            Color::new(ColorSpace::Oklch, 0.4, 0.1, 120.0, 1.0),
+            Color::new(ColorSpace::Oklch, 0.8, 0.1, 30.0, 1.0),
+            Color::new(ColorSpace::Oklch, 0.4, 0.0, None, 1.0),
+            Color::new(ColorSpace::Oklch, 0.4, 0.1, 60.0, 1.0),
+        ];
+        colors.sort_by(|a, b| a.oklch_sort_key().partial_cmp(&b.oklch_sort_key()).unwrap());
+
+        let keys: Vec<_> = colors.iter().map(Color::oklch_sort_key).collect();
+        assert_eq!(
+            keys,
+            [
+                (0.4, 0.0, -1.0),
+                (0.4, 0.1, 60.0),
+                (0.4, 0.1, 120.0),
+                (0.8, 0.1, 30.0),
+                (0.8, 0.1, 200.0),
+            ]
+        );
+
+        let gray = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.5, 1.0);
+        assert_eq!(gray.oklch_sort_key().2, -1.0);
+    }
+
+    #[test]
+    fn to_xyz() {
+        let white = Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let Components(x, y, z) = D65::WHITE_POINT;
+        assert_color_close(
+            &white.to_xyz_d65(),
+            &Color::new(ColorSpace::XyzD65, x, y, z, 1.0),
+            1.0e-4,
+        );
+
+        let Components(x, y, z) = D50::WHITE_POINT;
+        let d50_white = Color::new(ColorSpace::XyzD50, x, y, z, 1.0);
+        assert_color_close(&white.to_xyz_d50(), &d50_white, 1.0e-4);
+
+        // ProPhoto RGB is relative to D50, so its white is the D50 white
+        // point without adaptation.
+        let prophoto = Color::new(ColorSpace::ProphotoRgb, 1.0, 1.0, 1.0, 1.0);
+        assert_color_close(&prophoto.to_xyz_d50(), &d50_white, 1.0e-4);
+    }
+
+    #[test]
+    fn to_srgb() {
+        let lab = Color::new(ColorSpace::Lab, 56.6293, 39.2371, 57.5538, 0.5);
+        assert_color_close(
+            &lab.to_srgb(),
+            &Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 0.5),
+            1.0e-3,
+        );
+
+        let (red, green, blue, alpha) = lab.to_srgb_tuple();
+        assert!((red - 0.8235).abs() < 1.0e-3);
+        assert!((green - 0.4118).abs() < 1.0e-3);
+        assert!((blue - 0.1176).abs() < 1.0e-3);
+        assert_eq!(alpha, 0.5);
+
+        let missing = Color::new(ColorSpace::Srgb, None, 0.5, 1.0, None);
+        assert_eq!(missing.to_srgb_tuple(), (0.0, 0.5, 1.0, 0.0));
+    }
+
+    #[test]
+    fn to_rgba8_rounds_channels() {
+        let color = Color::new(ColorSpace::Srgb, 0.5, 1.2, None, 0.5);
+        assert_eq!(color.to_rgba8(), [128, 255, 0, 128]);
+
+        let lab = Color::new(ColorSpace::Lab, 100.0, 0.0, 0.0, 1.0);
+        assert_eq!(lab.to_rgba8(), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn to_linear_srgb() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5);
+        assert_color_close(
+            &red.to_linear_srgb(),
+            &Color::new(ColorSpace::SrgbLinear, 1.0, 0.0, 0.0, 0.5),
+            1.0e-5,
+        );
+
+        // The gamma curve above the linear segment, and the linear segment.
+        let gray = Color::new(ColorSpace::Srgb, 0.5, 0.5, 0.04, 1.0).to_linear_srgb();
+        assert_color_close(
+            &gray,
+            &Color::new(ColorSpace::SrgbLinear, 0.21404, 0.21404, 0.04 / 12.92, 1.0),
+            1.0e-4,
+        );
+
+        let linear = Color::new(ColorSpace::SrgbLinear, 0.25, 0.5, 0.75, 1.0);
+        assert_eq!(linear.to_linear_srgb(), linear);
+
+        let lab_white = Color::new(ColorSpace::Lab, 100.0, 0.0, 0.0, 1.0).to_linear_srgb();
+        assert_color_close(
+            &lab_white,
+            &Color::new(ColorSpace::SrgbLinear, 1.0, 1.0, 1.0, 1.0),
+            1.0e-4,
+        );
+    }
+
+    #[test]
+    fn from_bytes() {
+        assert_eq!(
+            Color::from([255, 0, 0]),
+            Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0)
+        );
+
+        let color = Color::from([0, 0, 0, 128]);
+        assert_eq!(color.color_space, ColorSpace::Srgb);
+        assert!((color.alpha - 0.502).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn rgba8_round_trip_is_stable() {
+        for value in 0..=255 {
+            let rgba = [value, 255 - value, value / 2, value];
+            assert_eq!(Color::from_rgba8(rgba).to_rgba8(), rgba);
+        }
+    }
+
+    /// A small deterministic generator, so tests can use many colors without
+    /// pulling in a dependency.
+    fn pseudo_random_colors(color_space: ColorSpace, count: usize) -> Vec<Color> {
+        let mut state = 0x2545_f491_u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+
+        (0..count)
+            .map(|_| Color::new(color_space, next(), next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    fn xyz_matrices() {
+        let to_xyz = ColorSpace::Srgb.to_xyz_matrix().unwrap();
+        let from_xyz = ColorSpace::Srgb.from_xyz_matrix().unwrap();
+
+        // Row-major, so the first row maps linear sRGB to X.
+        for (value, expected) in to_xyz[..3].iter().zip([0.4123908, 0.3575843, 0.1804808]) {
+            assert!((value - expected).abs() < 1.0e-4, "{value}");
+        }
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let product: f32 = (0..3)
+                    .map(|i| to_xyz[row * 3 + i] * from_xyz[i * 3 + column])
+                    .sum();
+                let expected = if row == column { 1.0 } else { 0.0 };
+                assert!((product - expected).abs() < 1.0e-5, "{product}");
+            }
+        }
+
+        // The matrices match the ones used for conversion.
+        let color = Color::new(ColorSpace::SrgbLinear, 0.25, 0.5, 0.75, 1.0);
+        let xyz = color.to_color_space(ColorSpace::XyzD65);
+        let mat = ColorSpace::SrgbLinear.to_xyz_matrix().unwrap();
+        for (row, expected) in [xyz.components.0, xyz.components.1, xyz.components.2]
+            .into_iter()
+            .enumerate()
+        {
+            let value = mat[row * 3] * 0.25 + mat[row * 3 + 1] * 0.5 + mat[row * 3 + 2] * 0.75;
+            assert!((value - expected).abs() < 1.0e-4, "{value} != {expected}");
+        }
+
+        assert!(ColorSpace::ProphotoRgb.to_xyz_matrix().is_some());
+        assert_eq!(ColorSpace::Lab.to_xyz_matrix(), None);
+        assert_eq!(ColorSpace::XyzD65.from_xyz_matrix(), None);
+    }
+
+    #[test]
+    fn oklab_matrices_are_inverses() {
+        for (mat, inverse) in [(XYZ_TO_LMS, LMS_TO_XYZ), (LMS_TO_OKLAB, OKLAB_TO_LMS)] {
+            for product in [mat.then(&inverse), inverse.then(&mat)] {
+                let identity = Transform::identity().to_array();
+                for (a, e) in product.to_array().into_iter().zip(identity) {
+                    assert!((a - e).abs() < 1.0e-6, "{a} is not equal to {e}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bradford_adaptation_matches_reference_matrices() {
+        #[rustfmt::skip]
+        const D50_TO_D65: Transform = Transform::new(
+             0.9554734527042182,   -0.028369706963208136,  0.012314001688319899, 0.0,
+            -0.023098536874261423,  1.0099954580058226,   -0.020507696433477912, 0.0,
+             0.0632593086610217,    0.021041398966943008,  1.3303659366080753,   0.0,
+             0.0,                   0.0,                   0.0,                  1.0,
+        );
+
+        #[rustfmt::skip]
+        const D65_TO_D50: Transform = Transform::new(
+             1.0479298208405488,    0.029627815688159344, -0.009243058152591178, 0.0,
+             0.022946793341019088,  0.990434484573249,     0.015055144896577895, 0.0,
+            -0.05019222954313557,  -0.01707382502938514,   0.7518742899580008,   0.0,
+             0.0,                   0.0,                   0.0,                  1.0,
+        );
+
+        for (actual, expected) in [
+            (
+                bradford_adaptation(&D50::WHITE_POINT, &D65::WHITE_POINT),
+                D50_TO_D65,
+            ),
+            (
+                bradford_adaptation(&D65::WHITE_POINT, &D50::WHITE_POINT),
+                D65_TO_D50,
+            ),
+        ] {
+            for (a, e) in actual.to_array().into_iter().zip(expected.to_array()) {
+                assert!((a - e).abs() < 1.0e-6, "{a} is not equal to {e}");
+            }
         }
     }
+
+    #[track_caller]
+    fn assert_xyz_close(actual: Components, expected: Components) {
+        assert_color_close(
+            &Color::new(ColorSpace::XyzD65, actual.0, actual.1, actual.2, 1.0),
+            &Color::new(ColorSpace::XyzD65, expected.0, expected.1, expected.2, 1.0),
+            1.0e-4,
+        );
+    }
+
+    #[test]
+    fn chromatic_adapt_maps_white_points() {
+        assert_xyz_close(
+            chromatic_adapt::<D50, D65>(&D50::WHITE_POINT),
+            D65::WHITE_POINT,
+        );
+        assert_xyz_close(
+            chromatic_adapt::<D65, D65>(&Components(0.25, 0.5, 0.75)),
+            Components(0.25, 0.5, 0.75),
+        );
+    }
+
+    #[test]
+    fn chromatic_adapt_to_photographic_white_points() {
+        use crate::{D55, D75};
+
+        assert_xyz_close(
+            chromatic_adapt::<D65, D55>(&D65::WHITE_POINT),
+            D55::WHITE_POINT,
+        );
+        assert_xyz_close(
+            chromatic_adapt::<D75, D65>(&D75::WHITE_POINT),
+            D65::WHITE_POINT,
+        );
+    }
+
+    #[test]
+    fn lab_relative_to_any_white_point() {
+        let white = XyzD50::new(
+            D50::WHITE_POINT.0,
+            D50::WHITE_POINT.1,
+            D50::WHITE_POINT.2,
+            ColorFlags::empty(),
+        );
+        assert_color_close(
+            &white.to_lab().into_color(1.0),
+            &Color::new(ColorSpace::Lab, 100.0, 0.0, 0.0, 1.0),
+            1.0e-4,
+        );
+
+        let white = XyzD65::new(
+            D65::WHITE_POINT.0,
+            D65::WHITE_POINT.1,
+            D65::WHITE_POINT.2,
+            ColorFlags::empty(),
+        );
+        let lab = white.to_lab();
+        assert_color_close(
+            &lab.to_xyz::<D65>().into_color(1.0),
+            &white.into_color(1.0),
+            1.0e-4,
+        );
+        assert_color_close(
+            &lab.into_color(1.0),
+            &Color::new(ColorSpace::Lab, 100.0, 0.0, 0.0, 1.0),
+            1.0e-4,
+        );
+    }
+
+    #[test]
+    fn rgb_shortcut_matches_conversion_through_xyz() {
+        let spaces = [
+            ColorSpace::Srgb,
+            ColorSpace::SrgbLinear,
+            ColorSpace::DisplayP3,
+            ColorSpace::A98Rgb,
+            ColorSpace::Rec2020,
+        ];
+
+        for from in spaces {
+            for color in pseudo_random_colors(from, 20) {
+                for to in spaces {
+                    if from == to {
+                        continue;
+                    }
+
+                    let shortcut = color.to_color_space(to);
+                    let through_xyz = color.to_color_space_with(to, None);
+                    assert!(
+                        shortcut.approx_eq(&through_xyz, 1.0e-5),
+                        "{from:?} -> {to:?}: {shortcut:?} != {through_xyz:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn srgb_to_oklab_shortcut_matches_conversion_through_xyz() {
+        // The hue is in degrees, so it needs a larger tolerance.
+        for color in pseudo_random_colors(ColorSpace::Srgb, 100) {
+            for (to, epsilon) in [(ColorSpace::Oklab, 1.0e-5), (ColorSpace::Oklch, 1.0e-3)] {
+                let shortcut = color.to_color_space(to);
+                let through_xyz = color.to_color_space(ColorSpace::XyzD50).to_color_space(to);
+                assert!(
+                    shortcut.approx_eq(&through_xyz, epsilon),
+                    "{shortcut:?} != {through_xyz:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn convert_slice_matches_element_wise_conversion() {
+        let colors = pseudo_random_colors(ColorSpace::Srgb, 100);
+
+        for dest in [ColorSpace::DisplayP3, ColorSpace::Oklch, ColorSpace::Srgb] {
+            let batch = Color::convert_slice(&colors, dest);
+            assert_eq!(batch.len(), colors.len());
+            for (converted, color) in batch.iter().zip(&colors) {
+                assert!(converted.approx_eq(&color.to_color_space(dest), 1.0e-6));
+            }
+        }
+    }
+
+    #[test]
+    fn convert_slice_mut_handles_mixed_color_spaces() {
+        let mut colors = vec![
+            Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0),
+            Color::new(ColorSpace::Lab, 50.0, 20.0, -30.0, 0.5),
+            Color::new(ColorSpace::Hsl, 120.0, 0.5, 0.5, 1.0),
+        ];
+        let expected: Vec<_> = colors
+            .iter()
+            .map(|color| color.to_color_space(ColorSpace::XyzD65))
+            .collect();
+
+        Color::convert_slice_mut(&mut colors, ColorSpace::XyzD65);
+        assert_eq!(colors, expected);
+    }
+
+    #[test]
+    fn missing_components_are_carried_forward() {
+        let hsl = Color::new(ColorSpace::Hsl, None, 0.5, 0.5, 1.0);
+        let lch = hsl.to_color_space(ColorSpace::Lch);
+        assert_eq!(lch.flags, ColorFlags::C2_IS_NONE);
+
+        let lab = Color::new(ColorSpace::Lab, None, 20.0, 30.0, None);
+        let oklch = lab.to_color_space(ColorSpace::Oklch);
+        assert_eq!(
+            oklch.flags,
+            ColorFlags::C0_IS_NONE | ColorFlags::ALPHA_IS_NONE
+        );
+
+        let srgb = Color::new(ColorSpace::Srgb, None, 0.5, 0.5, 1.0);
+        let xyz = srgb.to_color_space(ColorSpace::XyzD65);
+        assert_eq!(xyz.flags, ColorFlags::C0_IS_NONE);
+    }
+
+    #[test]
+    fn missing_components_without_analog_are_dropped() {
+        let srgb = Color::new(ColorSpace::Srgb, None, 0.4118, 0.1176, 1.0);
+        let lab = srgb.to_color_space(ColorSpace::Lab);
+        assert_eq!(lab.flags, ColorFlags::empty());
+
+        // The missing red is treated as 0.
+        let expected =
+            Color::new(ColorSpace::Srgb, 0.0, 0.4118, 0.1176, 1.0).to_color_space(ColorSpace::Lab);
+        assert_color_close(&lab, &expected, 1.0e-4);
+
+        let hwb = Color::new(ColorSpace::Hwb, 120.0, None, 0.25, 1.0);
+        let srgb = hwb.to_color_space(ColorSpace::Srgb);
+        assert_eq!(srgb.flags, ColorFlags::empty());
+    }
+
+    #[test]
+    fn powerless_components_become_missing() {
+        let gray = Color::new(
+            ColorSpace::Srgb,
+            128.0 / 255.0,
+            128.0 / 255.0,
+            128.0 / 255.0,
+            1.0,
+        );
+
+        let hsl = gray.to_color_space(ColorSpace::Hsl);
+        assert_eq!(hsl.flags, ColorFlags::C0_IS_NONE);
+
+        let hwb = gray.to_color_space(ColorSpace::Hwb);
+        assert_eq!(hwb.flags, ColorFlags::C0_IS_NONE);
+
+        let lch = gray.to_color_space(ColorSpace::Lch);
+        assert_eq!(lch.flags, ColorFlags::C2_IS_NONE);
+
+        let white = Color::new(ColorSpace::Lab, 100.0, 0.0, 0.0, 1.0);
+        let hsl = white.to_color_space(ColorSpace::Hsl);
+        assert_eq!(hsl.flags, ColorFlags::C0_IS_NONE | ColorFlags::C1_IS_NONE);
+
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        assert_eq!(
+            red.to_color_space(ColorSpace::Hsl).flags,
+            ColorFlags::empty()
+        );
+        assert_eq!(
+            red.to_color_space(ColorSpace::Oklch).flags,
+            ColorFlags::empty()
+        );
+    }
 }
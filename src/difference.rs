@@ -0,0 +1,286 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{Color, ColorSpace};
+
+impl Color {
+    /// The CIE76 color difference, which is the euclidean distance between the
+    /// two colors in CIE Lab. Missing components are treated as 0.
+    pub fn delta_e_76(&self, other: &Color) -> f32 {
+        let [l1, a1, b1] = self.resolved_components_in(ColorSpace::Lab);
+        let [l2, a2, b2] = other.resolved_components_in(ColorSpace::Lab);
+
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// The difference between the two colors as the euclidean distance in
+    /// Oklab, as used by the CSS gamut mapping algorithm. Missing components
+    /// are treated as 0.
+    /// <https://drafts.csswg.org/css-color-4/#color-difference-OK>
+    pub fn delta_e_ok(&self, other: &Color) -> f32 {
+        let [l1, a1, b1] = self.resolved_components_in(ColorSpace::Oklab);
+        let [l2, a2, b2] = other.resolved_components_in(ColorSpace::Oklab);
+
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// The CIEDE2000 color difference between the two colors in CIE Lab.
+    /// Missing components are treated as 0. The calculation is done in double
+    /// precision, because the hue terms are sensitive to rounding.
+    /// <https://hajim.rochester.edu/ece/sites/gsharma/ciede2000/>
+    pub fn delta_e_2000(&self, other: &Color) -> f32 {
+        const POW_25_7: f64 = 6103515625.0; // 25^7
+
+        let [l1, a1, b1] = self.resolved_components_in(ColorSpace::Lab).map(f64::from);
+        let [l2, a2, b2] = other.resolved_components_in(ColorSpace::Lab).map(f64::from);
+
+        // Adjust the a axis to compensate for the low chroma of near neutral
+        // colors.
+        let c1 = a1.hypot(b1);
+        let c2 = a2.hypot(b2);
+        let c_mean_7 = ((c1 + c2) / 2.0).powi(7);
+        let g = 0.5 * (1.0 - (c_mean_7 / (c_mean_7 + POW_25_7)).sqrt());
+
+        let a1 = (1.0 + g) * a1;
+        let a2 = (1.0 + g) * a2;
+
+        let c1 = a1.hypot(b1);
+        let c2 = a2.hypot(b2);
+
+        let hue = |a: f64, b: f64| {
+            if a == 0.0 && b == 0.0 {
+                0.0
+            } else {
+                b.atan2(a).to_degrees().rem_euclid(360.0)
+            }
+        };
+        let h1 = hue(a1, b1);
+        let h2 = hue(a2, b2);
+
+        // Differences in lightness, chroma and hue.
+        let delta_l = l2 - l1;
+        let delta_c = c2 - c1;
+        let chroma_product = c1 * c2;
+        let delta_h = if chroma_product == 0.0 {
+            0.0
+        } else {
+            let delta = h2 - h1;
+            if delta > 180.0 {
+                delta - 360.0
+            } else if delta < -180.0 {
+                delta + 360.0
+            } else {
+                delta
+            }
+        };
+        let delta_h = 2.0 * chroma_product.sqrt() * (delta_h / 2.0).to_radians().sin();
+
+        // Means of lightness, chroma and hue.
+        let l_mean = (l1 + l2) / 2.0;
+        let c_mean = (c1 + c2) / 2.0;
+        let h_mean = if chroma_product == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        // Weighting functions.
+        let t = 1.0 - 0.17 * (h_mean - 30.0).to_radians().cos()
+            + 0.24 * (2.0 * h_mean).to_radians().cos()
+            + 0.32 * (3.0 * h_mean + 6.0).to_radians().cos()
+            - 0.20 * (4.0 * h_mean - 63.0).to_radians().cos();
+
+        let l_mean_50_2 = (l_mean - 50.0).powi(2);
+        let sl = 1.0 + 0.015 * l_mean_50_2 / (20.0 + l_mean_50_2).sqrt();
+        let sc = 1.0 + 0.045 * c_mean;
+        let sh = 1.0 + 0.015 * c_mean * t;
+
+        // Rotation term for the blue region.
+        let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+        let c_mean_7 = c_mean.powi(7);
+        let rc = 2.0 * (c_mean_7 / (c_mean_7 + POW_25_7)).sqrt();
+        let rt = -(2.0 * delta_theta).to_radians().sin() * rc;
+
+        let l = delta_l / sl;
+        let c = delta_c / sc;
+        let h = delta_h / sh;
+
+        (l * l + c * c + h * h + rt * c * h).sqrt() as f32
+    }
+
+    /// Whether the two colors look the same, regardless of the color spaces
+    /// they are in. Both colors are converted to XYZ-D50 and each component,
+    /// and the alpha, may differ by no more than `epsilon`. Missing components
+    /// are treated as 0. Unlike `==`, this does not require the colors to be
+    /// in the same color space.
+    pub fn visually_eq(&self, other: &Color, epsilon: f32) -> bool {
+        let [x1, y1, z1] = self.resolved_components_in(ColorSpace::XyzD50);
+        let [x2, y2, z2] = other.resolved_components_in(ColorSpace::XyzD50);
+
+        [
+            (x1, x2),
+            (y1, y2),
+            (z1, z2),
+            (self.resolved_alpha(), other.resolved_alpha()),
+        ]
+        .into_iter()
+        .all(|(a, b)| (a - b).abs() <= epsilon)
+    }
+
+    /// The color in `palette` that is closest to this color by
+    /// [`Color::delta_e_2000`], or `None` if the palette is empty. If several
+    /// colors are equally close, the first one is returned.
+    pub fn nearest<'a>(&self, palette: &'a [Color]) -> Option<&'a Color> {
+        palette
+            .iter()
+            .map(|color| (color, self.delta_e_2000(color)))
+            .reduce(|best, candidate| {
+                if candidate.1 < best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            })
+            .map(|(color, _)| color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta_e_76() {
+        let lab = Color::new(ColorSpace::Lab, 50.0, 20.0, -30.0, 1.0);
+        let near = Color::new(ColorSpace::Lab, 50.3, 20.0, -30.4, 1.0);
+        let far = Color::new(ColorSpace::Lab, 90.0, -40.0, 10.0, 1.0);
+
+        assert_eq!(lab.delta_e_76(&lab), 0.0);
+        assert!((lab.delta_e_76(&near) - 0.5).abs() < 1.0e-4);
+        assert!((lab.delta_e_76(&far) - 82.46211).abs() < 1.0e-3);
+        assert_eq!(lab.delta_e_76(&far), far.delta_e_76(&lab));
+    }
+
+    #[test]
+    fn delta_e_76_treats_missing_components_as_zero() {
+        let missing = Color::new(ColorSpace::Lab, 50.0, None, None, 1.0);
+        let gray = Color::new(ColorSpace::Lab, 50.0, 0.0, 0.0, 1.0);
+
+        assert_eq!(missing.delta_e_76(&gray), 0.0);
+    }
+
+    #[test]
+    fn delta_e_ok() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+        let black = Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let white = Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 1.0);
+
+        assert_eq!(red.delta_e_ok(&red), 0.0);
+        assert_eq!(red.delta_e_ok(&blue), blue.delta_e_ok(&red));
+        assert!((black.delta_e_ok(&white) - 1.0).abs() < 1.0e-3);
+
+        let oklab = Color::new(ColorSpace::Oklab, 0.5, 0.1, -0.1, 1.0);
+        let other = Color::new(ColorSpace::Oklab, 0.5, 0.13, -0.06, 1.0);
+        assert!((oklab.delta_e_ok(&other) - 0.05).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn delta_e_2000() {
+        // Test data from Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference
+        // Formula: Implementation Notes, Supplementary Test Data, and
+        // Mathematical Observations".
+        #[rustfmt::skip]
+        let pairs = [
+            (50.0000,   2.6772, -79.7751, 50.0000,   0.0000, -82.7485,  2.0425),
+            (50.0000,   3.1571, -77.2803, 50.0000,   0.0000, -82.7485,  2.8615),
+            (50.0000,   2.8361, -74.0200, 50.0000,   0.0000, -82.7485,  3.4412),
+            (50.0000,  -1.3802, -84.2814, 50.0000,   0.0000, -82.7485,  1.0000),
+            (50.0000,  -1.1848, -84.8006, 50.0000,   0.0000, -82.7485,  1.0000),
+            (50.0000,  -0.9009, -85.5211, 50.0000,   0.0000, -82.7485,  1.0000),
+            (50.0000,   0.0000,   0.0000, 50.0000,  -1.0000,   2.0000,  2.3669),
+            (50.0000,  -1.0000,   2.0000, 50.0000,   0.0000,   0.0000,  2.3669),
+            (50.0000,   2.4900,  -0.0010, 50.0000,  -2.4900,   0.0009,  7.1792),
+            (50.0000,   2.4900,  -0.0010, 50.0000,  -2.4900,   0.0010,  7.1792),
+            (50.0000,   2.4900,  -0.0010, 50.0000,  -2.4900,   0.0011,  7.2195),
+            (50.0000,   2.4900,  -0.0010, 50.0000,  -2.4900,   0.0012,  7.2195),
+            (50.0000,  -0.0010,   2.4900, 50.0000,   0.0009,  -2.4900,  4.8045),
+            (50.0000,  -0.0010,   2.4900, 50.0000,   0.0010,  -2.4900,  4.8045),
+            (50.0000,  -0.0010,   2.4900, 50.0000,   0.0011,  -2.4900,  4.7461),
+            (50.0000,   2.5000,   0.0000, 50.0000,   0.0000,  -2.5000,  4.3065),
+            (50.0000,   2.5000,   0.0000, 73.0000,  25.0000, -18.0000, 27.1492),
+            (50.0000,   2.5000,   0.0000, 61.0000,  -5.0000,  29.0000, 22.8977),
+            (50.0000,   2.5000,   0.0000, 56.0000, -27.0000,  -3.0000, 31.9030),
+            (50.0000,   2.5000,   0.0000, 58.0000,  24.0000,  15.0000, 19.4535),
+            (50.0000,   2.5000,   0.0000, 50.0000,   3.1736,   0.5854,  1.0000),
+            (50.0000,   2.5000,   0.0000, 50.0000,   3.2972,   0.0000,  1.0000),
+            (50.0000,   2.5000,   0.0000, 50.0000,   1.8634,   0.5757,  1.0000),
+            (50.0000,   2.5000,   0.0000, 50.0000,   3.2592,   0.3350,  1.0000),
+            (60.2574, -34.0099,  36.2677, 60.4626, -34.1751,  39.4387,  1.2644),
+            (63.0109, -31.0961,  -5.8663, 62.8187, -29.7946,  -4.0864,  1.2630),
+            (61.2901,   3.7196,  -5.3901, 61.4292,   2.2480,  -4.9620,  1.8731),
+            (35.0831, -44.1164,   3.7933, 35.0232, -40.0716,   1.5901,  1.8645),
+            (22.7233,  20.0904, -46.6940, 23.0331,  14.9730, -42.5619,  2.0373),
+            (36.4612,  47.8580,  18.3852, 36.2715,  50.5065,  21.2231,  1.4146),
+            (90.8027,  -2.0831,   1.4410, 91.1528,  -1.6435,   0.0447,  1.4441),
+            (90.9257,  -0.5406,  -0.9208, 88.6381,  -0.8985,  -0.7239,  1.5381),
+            ( 6.7747,  -0.2908,  -2.4247,  5.8714,  -0.0985,  -2.2286,  0.6377),
+            ( 2.0776,   0.0795,  -1.1350,  0.9033,  -0.0636,  -0.5514,  0.9082),
+        ];
+
+        for (l1, a1, b1, l2, a2, b2, expected) in pairs {
+            let first = Color::new(ColorSpace::Lab, l1, a1, b1, 1.0);
+            let second = Color::new(ColorSpace::Lab, l2, a2, b2, 1.0);
+
+            for delta_e in [first.delta_e_2000(&second), second.delta_e_2000(&first)] {
+                assert!(
+                    (delta_e - expected).abs() < 1.0e-4,
+                    "{delta_e} is not equal to {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn delta_e_76_converts_to_lab() {
+        let black = Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let white = Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 1.0);
+
+        assert!((black.delta_e_76(&white) - 100.0).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn visually_eq() {
+        let red = Color::parse("rgb(255 0 0)").unwrap();
+        let lab = Color::parse("lab(54.29 80.8 69.89)").unwrap();
+        assert_ne!(red, lab);
+        assert!(red.visually_eq(&lab, 1.0e-3));
+        assert!(red.visually_eq(&red.to_color_space(ColorSpace::Oklch), 1.0e-5));
+
+        let orange = Color::parse("rgb(255 128 0)").unwrap();
+        assert!(!red.visually_eq(&orange, 1.0e-3));
+        assert!(!red.visually_eq(&red.clone().with_alpha(0.5), 1.0e-3));
+    }
+
+    #[test]
+    fn nearest() {
+        let palette = [
+            Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 1.0),
+            Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0),
+            Color::new(ColorSpace::Srgb, 0.0, 1.0, 0.0, 1.0),
+            Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0),
+            Color::new(ColorSpace::Srgb, 1.0, 1.0, 1.0, 1.0),
+        ];
+
+        let off_red = Color::new(ColorSpace::Srgb, 0.9, 0.1, 0.05, 1.0);
+        assert_eq!(off_red.nearest(&palette), Some(&palette[1]));
+
+        let lab_red = palette[1].to_color_space(ColorSpace::Lab);
+        assert_eq!(lab_red.nearest(&palette), Some(&palette[1]));
+
+        assert_eq!(off_red.nearest(&[]), None);
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{Color, ColorFlags, ColorSpace, Components};
+
+/// The RGB color space that bounds the gamut of the given color space, or
+/// `None` if the color space is unbounded.
+fn gamut_space(color_space: ColorSpace) -> Option<ColorSpace> {
+    match color_space {
+        ColorSpace::Srgb
+        | ColorSpace::SrgbLinear
+        | ColorSpace::DisplayP3
+        | ColorSpace::A98Rgb
+        | ColorSpace::ProphotoRgb
+        | ColorSpace::Rec2020 => Some(color_space),
+        ColorSpace::Hsl | ColorSpace::Hwb => Some(ColorSpace::Srgb),
+        ColorSpace::Lab
+        | ColorSpace::Lch
+        | ColorSpace::Oklab
+        | ColorSpace::Oklch
+        | ColorSpace::XyzD50
+        | ColorSpace::XyzD65 => None,
+    }
+}
+
+/// The largest Oklch chroma that keeps a color with the given lightness and
+/// hue, in degrees, inside the sRGB gamut. This is the outline of the gamut
+/// that color pickers draw. The chroma is found with a binary search, and the
+/// result is always in gamut. Lightness outside of `(0, 1)` gives 0.
+pub fn max_srgb_chroma_oklch(lightness: f32, hue: f32) -> f32 {
+    /// Larger than the chroma of any sRGB color, which peaks around 0.32.
+    const MAX_CHROMA: f32 = 0.4;
+    /// The precision of the chroma search.
+    const EPSILON: f32 = 1.0e-5;
+
+    if lightness <= 0.0 || lightness >= 1.0 {
+        return 0.0;
+    }
+
+    let mut min = 0.0;
+    let mut max = MAX_CHROMA;
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.0;
+        let color = Color::new(ColorSpace::Oklch, lightness, chroma, hue, 1.0);
+        if color.is_in_gamut(ColorSpace::Srgb) {
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    min
+}
+
+impl Color {
+    /// Whether the color fits inside the gamut of the given color space, with
+    /// a small tolerance for rounding errors picked up during conversion. HSL
+    /// and HWB share the gamut of sRGB. Color spaces without bounds, like Lab,
+    /// Oklch and XYZ, contain every color.
+    pub fn is_in_gamut(&self, color_space: ColorSpace) -> bool {
+        /// Tolerance for rounding errors picked up during conversion.
+        const EPSILON: f32 = 1.0e-5;
+
+        let Some(gamut_space) = gamut_space(color_space) else {
+            return true;
+        };
+
+        self.resolved_components_in(gamut_space)
+            .iter()
+            .all(|value| (-EPSILON..=1.0 + EPSILON).contains(value))
+    }
+
+    /// Convert the color to the given color space and clamp each channel to
+    /// the gamut of that space, leaving alpha and missing components as they
+    /// are. This is the "clip" step of the CSS gamut mapping algorithm.
+    /// <https://drafts.csswg.org/css-color-4/#clip>
+    pub fn clip_to_gamut(&self, color_space: ColorSpace) -> Color {
+        let Some(gamut_space) = gamut_space(color_space) else {
+            return self.to_color_space(color_space);
+        };
+
+        let mut clipped = self.to_color_space(gamut_space);
+        for i in 0..3 {
+            let value = clipped.components[i];
+            if !value.is_nan() {
+                clipped.components[i] = value.clamp(0.0, 1.0);
+            }
+        }
+
+        clipped.to_color_space(color_space)
+    }
+
+    /// Map the color into the gamut of the given color space by reducing its
+    /// chroma in Oklch while keeping the lightness and hue fixed, until the
+    /// clipped result is within a just noticeable difference of it.
+    /// <https://drafts.csswg.org/css-color-4/#css-gamut-mapping>
+    pub fn map_to_gamut(&self, dest: ColorSpace) -> Color {
+        /// The just noticeable difference in Oklab.
+        const JND: f32 = 0.02;
+        /// The precision of the chroma search.
+        const EPSILON: f32 = 0.0001;
+
+        if gamut_space(dest).is_none() {
+            return self.to_color_space(dest);
+        }
+
+        let origin = self.to_color_space(ColorSpace::Oklch);
+        let Components(lightness, chroma, _) = origin.components;
+
+        // Lightness outside of the range maps to white or black.
+        if lightness >= 1.0 || lightness <= 0.0 {
+            let mut result = origin;
+            result.components = Components(lightness.clamp(0.0, 1.0), 0.0, 0.0);
+            result.flags &= ColorFlags::ALPHA_IS_NONE;
+            return result.to_color_space(dest);
+        }
+
+        if origin.is_in_gamut(dest) {
+            return origin.clip_to_gamut(dest);
+        }
+
+        let mut current = origin;
+        let mut clipped = current.clip_to_gamut(dest);
+        if clipped.delta_e_ok(&current) < JND {
+            return clipped;
+        }
+
+        let mut min = 0.0;
+        let mut max = chroma;
+        let mut min_in_gamut = true;
+
+        while max - min > EPSILON {
+            let chroma = (min + max) / 2.0;
+            current.components.1 = chroma;
+
+            if min_in_gamut && current.is_in_gamut(dest) {
+                min = chroma;
+                continue;
+            }
+
+            clipped = current.clip_to_gamut(dest);
+            let delta_e = clipped.delta_e_ok(&current);
+            if delta_e < JND {
+                if JND - delta_e < EPSILON {
+                    return clipped;
+                }
+                min_in_gamut = false;
+                min = chroma;
+            } else {
+                max = chroma;
+            }
+        }
+
+        clipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! assert_components {
+        ($color:expr, $c0:expr, $c1:expr, $c2:expr) => {{
+            let color = &$color;
+            for (actual, expected) in [
+                (color.components.0, $c0),
+                (color.components.1, $c1),
+                (color.components.2, $c2),
+            ] {
+                assert!(
+                    (actual - expected).abs() < 1.0e-3,
+                    "{actual} is not equal to {expected}"
+                );
+            }
+        }};
+    }
+
+    #[test]
+    fn is_in_gamut() {
+        let color = Color::new(ColorSpace::Srgb, 0.25, 0.5, 1.0, 1.0);
+        assert!(color.is_in_gamut(ColorSpace::Srgb));
+        assert!(color.is_in_gamut(ColorSpace::DisplayP3));
+        assert!(color.is_in_gamut(ColorSpace::Hsl));
+
+        let lab = Color::new(ColorSpace::Lab, 50.0, 120.0, 0.0, 1.0);
+        assert!(!lab.is_in_gamut(ColorSpace::Srgb));
+        assert!(!lab.is_in_gamut(ColorSpace::Hwb));
+        assert!(lab.is_in_gamut(ColorSpace::Lab));
+        assert!(lab.is_in_gamut(ColorSpace::XyzD65));
+
+        let red = Color::new(ColorSpace::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+        assert!(red.is_in_gamut(ColorSpace::DisplayP3));
+        assert!(!red.is_in_gamut(ColorSpace::Srgb));
+
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        assert!(red.is_in_gamut(ColorSpace::DisplayP3));
+    }
+
+    #[test]
+    fn clip_to_gamut() {
+        let color = Color::new(ColorSpace::Srgb, 1.2, -0.1, 0.5, 0.5);
+        let clipped = color.clip_to_gamut(ColorSpace::Srgb);
+        assert_eq!(clipped, Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.5, 0.5));
+
+        let missing = Color::new(ColorSpace::Srgb, 1.2, None, 0.5, None);
+        let clipped = missing.clip_to_gamut(ColorSpace::Srgb);
+        assert_eq!(clipped, Color::new(ColorSpace::Srgb, 1.0, None, 0.5, None));
+
+        let red = Color::new(ColorSpace::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+        let clipped = red.clip_to_gamut(ColorSpace::Srgb);
+        assert_components!(clipped, 1.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn map_display_p3_to_srgb() {
+        let red = Color::new(ColorSpace::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+        let mapped = red.map_to_gamut(ColorSpace::Srgb);
+        assert_eq!(mapped.color_space, ColorSpace::Srgb);
+        assert_components!(mapped, 1.0, 0.04457, 0.04593);
+
+        let green = Color::new(ColorSpace::DisplayP3, 0.0, 1.0, 0.0, 0.5);
+        let mapped = green.map_to_gamut(ColorSpace::Srgb);
+        assert_components!(mapped, 0.0, 0.98576, 0.15974);
+        assert_eq!(mapped.alpha, 0.5);
+    }
+
+    #[test]
+    fn map_keeps_lightness_and_hue() {
+        let red = Color::new(ColorSpace::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+        let origin = red.to_color_space(ColorSpace::Oklch);
+        let mapped = red
+            .map_to_gamut(ColorSpace::Srgb)
+            .to_color_space(ColorSpace::Oklch);
+
+        assert!((mapped.components.0 - origin.components.0).abs() < 0.02);
+        assert!(mapped.components.1 < origin.components.1);
+        assert!((mapped.components.2 - origin.components.2).abs() < 2.0);
+    }
+
+    #[test]
+    fn colors_in_gamut_are_unchanged() {
+        let color = Color::new(ColorSpace::Srgb, 0.25, 0.5, 0.75, 1.0);
+        let mapped = color.map_to_gamut(ColorSpace::Srgb);
+        assert_components!(mapped, 0.25, 0.5, 0.75);
+
+        let lab = Color::new(ColorSpace::Lab, 50.0, 120.0, 0.0, 1.0);
+        assert_eq!(lab.map_to_gamut(ColorSpace::Lab), lab);
+    }
+
+    #[test]
+    fn lightness_out_of_range_maps_to_white_or_black() {
+        let white = Color::new(ColorSpace::Oklch, 1.2, 0.3, 120.0, 1.0);
+        assert_components!(white.map_to_gamut(ColorSpace::Srgb), 1.0, 1.0, 1.0);
+
+        let black = Color::new(ColorSpace::Oklch, -0.1, 0.3, 120.0, 1.0);
+        assert_components!(black.map_to_gamut(ColorSpace::Srgb), 0.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn max_srgb_chroma_oklch() {
+        for (lightness, hue) in [(0.62796, 29.23), (0.5, 264.0), (0.9, 110.0), (0.3, 180.0)] {
+            let chroma = super::max_srgb_chroma_oklch(lightness, hue);
+            assert!(chroma > 0.0);
+
+            let edge = Color::new(ColorSpace::Oklch, lightness, chroma, hue, 1.0);
+            assert!(edge.is_in_gamut(ColorSpace::Srgb));
+            assert!(!edge
+                .with_component(1, chroma + 1.0e-3)
+                .is_in_gamut(ColorSpace::Srgb));
+        }
+
+        // The most saturated sRGB red sits on the boundary.
+        let chroma = super::max_srgb_chroma_oklch(0.62796, 29.2339);
+        assert!((chroma - 0.25768).abs() < 1.0e-3, "{chroma}");
+
+        assert_eq!(super::max_srgb_chroma_oklch(0.0, 120.0), 0.0);
+        assert_eq!(super::max_srgb_chroma_oklch(1.0, 120.0), 0.0);
+    }
+}
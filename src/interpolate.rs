@@ -0,0 +1,629 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{model::wrap_hue, Color, ColorFlags, ColorSpace, Components};
+use alloc::{vec, vec::Vec};
+
+/// How to interpolate between two hue angles.
+/// <https://drafts.csswg.org/css-color-4/#hue-interpolation>
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HueInterpolationMethod {
+    /// Take the shortest way around the hue circle.
+    #[default]
+    Shorter,
+    /// Take the longest way around the hue circle.
+    Longer,
+    /// Always move in the positive direction around the hue circle.
+    Increasing,
+    /// Always move in the negative direction around the hue circle.
+    Decreasing,
+}
+
+/// Adjust a pair of hues so that linear interpolation between them follows the
+/// requested path around the hue circle. Both hues are first normalized into
+/// `[0, 360)`; one of them may then be moved up by 360 degrees.
+/// <https://drafts.csswg.org/css-color-4/#hue-interpolation>
+pub fn adjust_hue_pair(h1: f32, h2: f32, method: HueInterpolationMethod) -> (f32, f32) {
+    let mut h1 = wrap_hue(h1);
+    let mut h2 = wrap_hue(h2);
+    let delta = h2 - h1;
+
+    match method {
+        HueInterpolationMethod::Shorter => {
+            if delta > 180.0 {
+                h1 += 360.0;
+            } else if delta < -180.0 {
+                h2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Longer => {
+            if 0.0 < delta && delta < 180.0 {
+                h1 += 360.0;
+            } else if -180.0 < delta && delta <= 0.0 {
+                h2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Increasing => {
+            if h2 < h1 {
+                h2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Decreasing => {
+            if h1 < h2 {
+                h1 += 360.0;
+            }
+        }
+    }
+
+    (h1, h2)
+}
+
+impl Color {
+    /// Multiply the components of the color by its alpha. Hue components are
+    /// angles, not amounts, so they are left as they are. A missing alpha is
+    /// treated as 1.
+    /// <https://drafts.csswg.org/css-color-4/#interpolation-alpha>
+    pub fn premultiply(&self) -> Color {
+        self.scale_by_alpha(|value, alpha| value * alpha)
+    }
+
+    /// Divide the components of a premultiplied color by its alpha, undoing
+    /// [`Color::premultiply`]. Colors with an alpha of 0 are left as they are.
+    pub fn unpremultiply(&self) -> Color {
+        if self.alpha == 0.0 {
+            return self.clone();
+        }
+        self.scale_by_alpha(|value, alpha| value / alpha)
+    }
+
+    fn scale_by_alpha(&self, scale: impl Fn(f32, f32) -> f32) -> Color {
+        let alpha = if self.flags.contains(ColorFlags::ALPHA_IS_NONE) {
+            1.0
+        } else {
+            self.alpha
+        };
+
+        let mut result = self.clone();
+        for i in 0..3 {
+            if Some(i) != self.color_space.hue_index() {
+                result.components[i] = scale(self.components[i], alpha);
+            }
+        }
+        result
+    }
+
+    /// Interpolate between this color and `other` in the given color space,
+    /// where `t` of 0 is this color and `t` of 1 is `other`. Components are
+    /// premultiplied by alpha and a missing component takes the value from the
+    /// other color.
+    /// <https://drafts.csswg.org/css-color-4/#interpolation>
+    pub fn interpolate(
+        &self,
+        other: &Color,
+        t: f32,
+        space: ColorSpace,
+        hue: HueInterpolationMethod,
+    ) -> Color {
+        self.interpolate_with(other, t, space, hue, true)
+    }
+
+    /// Interpolate like [`Color::interpolate`], but without premultiplying
+    /// the components by alpha. Each component, and the alpha, is interpolated
+    /// on its own, so a mostly transparent color pulls the result towards its
+    /// components as much as an opaque one does. This is what pipelines that
+    /// already work with premultiplied colors want.
+    pub fn interpolate_straight(
+        &self,
+        other: &Color,
+        t: f32,
+        space: ColorSpace,
+        hue: HueInterpolationMethod,
+    ) -> Color {
+        self.interpolate_with(other, t, space, hue, false)
+    }
+
+    /// Interpolate like [`Color::interpolate`], with the halfway point between
+    /// the colors moved to `midpoint`, like a CSS gradient color hint. `t` is
+    /// remapped to `t.powf(ln(0.5) / ln(midpoint))`, so a `midpoint` of 0.5
+    /// is linear and at `t == midpoint` the colors are mixed evenly. A
+    /// `midpoint` of 0 or less gives `other` and 1 or more gives this color,
+    /// except at the ends.
+    /// <https://drafts.csswg.org/css-images-4/#color-transition-hint>
+    pub fn interpolate_eased(
+        &self,
+        other: &Color,
+        t: f32,
+        space: ColorSpace,
+        hue: HueInterpolationMethod,
+        midpoint: f32,
+    ) -> Color {
+        let t = if t <= 0.0 || t >= 1.0 {
+            t
+        } else if midpoint <= 0.0 {
+            1.0
+        } else if midpoint >= 1.0 {
+            0.0
+        } else {
+            t.powf(0.5_f32.ln() / midpoint.ln())
+        };
+        self.interpolate(other, t, space, hue)
+    }
+
+    fn interpolate_with(
+        &self,
+        other: &Color,
+        t: f32,
+        space: ColorSpace,
+        hue: HueInterpolationMethod,
+        premultiply: bool,
+    ) -> Color {
+        const FLAGS: [ColorFlags; 4] = [
+            ColorFlags::C0_IS_NONE,
+            ColorFlags::C1_IS_NONE,
+            ColorFlags::C2_IS_NONE,
+            ColorFlags::ALPHA_IS_NONE,
+        ];
+
+        let from = self.to_color_space(space);
+        let to = other.to_color_space(space);
+
+        let values = |color: &Color| {
+            let Components(c0, c1, c2) = color.components;
+            let mut values = [c0, c1, c2, color.alpha].map(Some);
+            for (value, flag) in values.iter_mut().zip(FLAGS) {
+                if color.flags.contains(flag) || value.is_some_and(f32::is_nan) {
+                    *value = None;
+                }
+            }
+            values
+        };
+
+        let mut from_values = values(&from);
+        let mut to_values = values(&to);
+
+        // A missing component takes the value of the other color.
+        let mut flags = ColorFlags::empty();
+        for i in 0..4 {
+            match (from_values[i], to_values[i]) {
+                (None, None) => flags |= FLAGS[i],
+                (None, value) => from_values[i] = value,
+                (value, None) => to_values[i] = value,
+                _ => {}
+            }
+        }
+
+        let hue_index = space.hue_index();
+        if let Some(i) = hue_index {
+            if let (Some(h1), Some(h2)) = (from_values[i], to_values[i]) {
+                let (h1, h2) = adjust_hue_pair(h1, h2, hue);
+                from_values[i] = Some(h1);
+                to_values[i] = Some(h2);
+            }
+        }
+
+        let from_alpha = from_values[3].unwrap_or(1.0);
+        let to_alpha = to_values[3].unwrap_or(1.0);
+        let alpha = from_alpha + (to_alpha - from_alpha) * t;
+
+        let mut components = [0.0; 3];
+        for (i, component) in components.iter_mut().enumerate() {
+            let (Some(from), Some(to)) = (from_values[i], to_values[i]) else {
+                continue;
+            };
+
+            *component = if Some(i) == hue_index {
+                wrap_hue(from + (to - from) * t)
+            } else if !premultiply {
+                from + (to - from) * t
+            } else {
+                let from = from * from_alpha;
+                let to = to * to_alpha;
+                let value = from + (to - from) * t;
+                if alpha == 0.0 {
+                    value
+                } else {
+                    value / alpha
+                }
+            };
+        }
+
+        let [c0, c1, c2] = components;
+        Color {
+            components: Components(c0, c1, c2),
+            flags,
+            color_space: space,
+            alpha: if flags.contains(ColorFlags::ALPHA_IS_NONE) {
+                0.0
+            } else {
+                alpha
+            },
+        }
+    }
+}
+
+impl Color {
+    /// Return `n` evenly spaced colors from this color to `other`, both
+    /// included, interpolated in the given color space. With `n` of 1 only
+    /// this color is returned.
+    pub fn gradient_steps(
+        &self,
+        other: &Color,
+        n: usize,
+        space: ColorSpace,
+        hue: HueInterpolationMethod,
+    ) -> Vec<Color> {
+        match n {
+            0 => vec![],
+            1 => vec![self.clone()],
+            _ => (0..n)
+                .map(|i| self.interpolate(other, i as f32 / (n - 1) as f32, space, hue))
+                .collect(),
+        }
+    }
+
+    /// Mix this color with `other` the same way as CSS `color-mix()`, where
+    /// `weight1` is the fraction of this color in the result, e.g. 0.2 for
+    /// `color-mix(in srgb, red 20%, blue)`. The weight of `other` is
+    /// `1 - weight1`.
+    /// <https://drafts.csswg.org/css-color-5/#color-mix>
+    pub fn mix(
+        &self,
+        other: &Color,
+        weight1: f32,
+        space: ColorSpace,
+        hue: HueInterpolationMethod,
+    ) -> Color {
+        let weight1 = weight1.clamp(0.0, 1.0);
+        self.interpolate(other, 1.0 - weight1, space, hue)
+    }
+
+    /// Mix this color with `other` with separate weights for each color, either
+    /// of which may be omitted, following the percentage normalization rules
+    /// of `color-mix()`. Weights that don't sum to 1 are scaled so that they
+    /// do, and if they sum to less than 1 the alpha of the result is multiplied
+    /// by the sum. Returns `None` if a weight is negative or both weights are 0.
+    /// <https://drafts.csswg.org/css-color-5/#color-mix-percent-norm>
+    pub fn mix_weighted(
+        &self,
+        weight1: Option<f32>,
+        other: &Color,
+        weight2: Option<f32>,
+        space: ColorSpace,
+        hue: HueInterpolationMethod,
+    ) -> Option<Color> {
+        let (weight1, weight2) = match (weight1, weight2) {
+            (None, None) => (0.5, 0.5),
+            (Some(weight1), None) => (weight1, 1.0 - weight1),
+            (None, Some(weight2)) => (1.0 - weight2, weight2),
+            (Some(weight1), Some(weight2)) => (weight1, weight2),
+        };
+
+        if !(0.0..=1.0).contains(&weight1) || !(0.0..=1.0).contains(&weight2) {
+            return None;
+        }
+
+        let sum = weight1 + weight2;
+        if sum == 0.0 {
+            return None;
+        }
+
+        let mut result = self.interpolate(other, weight2 / sum, space, hue);
+        if sum < 1.0 && !result.flags.contains(ColorFlags::ALPHA_IS_NONE) {
+            result.alpha *= sum;
+        }
+
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! assert_components {
+        ($color:expr, $c0:expr, $c1:expr, $c2:expr, $alpha:expr) => {{
+            let color = &$color;
+            for (actual, expected) in [
+                (color.components.0, $c0),
+                (color.components.1, $c1),
+                (color.components.2, $c2),
+                (color.alpha, $alpha),
+            ] {
+                assert!(
+                    (actual - expected).abs() < 1.0e-4,
+                    "{actual} is not equal to {expected}"
+                );
+            }
+        }};
+    }
+
+    #[test]
+    fn adjust_hue_pair_shorter() {
+        use HueInterpolationMethod::Shorter;
+
+        assert_eq!(adjust_hue_pair(40.0, 330.0, Shorter), (400.0, 330.0));
+        assert_eq!(adjust_hue_pair(330.0, 40.0, Shorter), (330.0, 400.0));
+        assert_eq!(adjust_hue_pair(40.0, 60.0, Shorter), (40.0, 60.0));
+        assert_eq!(adjust_hue_pair(-30.0, 370.0, Shorter), (330.0, 370.0));
+    }
+
+    #[test]
+    fn adjust_hue_pair_longer() {
+        use HueInterpolationMethod::Longer;
+
+        assert_eq!(adjust_hue_pair(40.0, 330.0, Longer), (40.0, 330.0));
+        assert_eq!(adjust_hue_pair(40.0, 60.0, Longer), (400.0, 60.0));
+        assert_eq!(adjust_hue_pair(60.0, 40.0, Longer), (60.0, 400.0));
+        assert_eq!(adjust_hue_pair(90.0, 90.0, Longer), (90.0, 450.0));
+    }
+
+    #[test]
+    fn adjust_hue_pair_increasing() {
+        use HueInterpolationMethod::Increasing;
+
+        assert_eq!(adjust_hue_pair(40.0, 330.0, Increasing), (40.0, 330.0));
+        assert_eq!(adjust_hue_pair(330.0, 40.0, Increasing), (330.0, 400.0));
+    }
+
+    #[test]
+    fn adjust_hue_pair_decreasing() {
+        use HueInterpolationMethod::Decreasing;
+
+        assert_eq!(adjust_hue_pair(40.0, 330.0, Decreasing), (400.0, 330.0));
+        assert_eq!(adjust_hue_pair(330.0, 40.0, Decreasing), (330.0, 40.0));
+    }
+
+    #[test]
+    fn red_to_blue_in_oklab() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+
+        let mid = red.interpolate(
+            &blue,
+            0.5,
+            ColorSpace::Oklab,
+            HueInterpolationMethod::Shorter,
+        );
+        assert_eq!(mid.color_space, ColorSpace::Oklab);
+        assert_components!(mid, 0.53998, 0.09620, -0.09284, 1.0);
+
+        let srgb = mid.to_color_space(ColorSpace::Srgb);
+        assert_components!(srgb, 0.55044, 0.32562, 0.63650, 1.0);
+    }
+
+    #[test]
+    fn red_to_blue_in_hsl() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+
+        let shorter = red.interpolate(&blue, 0.5, ColorSpace::Hsl, HueInterpolationMethod::Shorter);
+        assert_components!(shorter, 300.0, 1.0, 0.5, 1.0);
+
+        let longer = red.interpolate(&blue, 0.5, ColorSpace::Hsl, HueInterpolationMethod::Longer);
+        assert_components!(longer, 120.0, 1.0, 0.5, 1.0);
+    }
+
+    #[test]
+    fn interpolation_is_premultiplied() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 0.5);
+
+        let mid = red.interpolate(
+            &blue,
+            0.5,
+            ColorSpace::Srgb,
+            HueInterpolationMethod::Shorter,
+        );
+        assert_components!(mid, 2.0 / 3.0, 0.0, 1.0 / 3.0, 0.75);
+    }
+
+    #[test]
+    fn straight_interpolation_ignores_alpha() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 0.5);
+
+        let mid = red.interpolate_straight(
+            &blue,
+            0.5,
+            ColorSpace::Srgb,
+            HueInterpolationMethod::Shorter,
+        );
+        assert_components!(mid, 0.5, 0.0, 0.5, 0.75);
+
+        // With equal alphas, premultiplying makes no difference.
+        let opaque_blue = blue.clone().with_alpha(1.0);
+        let premultiplied = red.interpolate(
+            &opaque_blue,
+            0.25,
+            ColorSpace::Oklab,
+            HueInterpolationMethod::Shorter,
+        );
+        let straight = red.interpolate_straight(
+            &opaque_blue,
+            0.25,
+            ColorSpace::Oklab,
+            HueInterpolationMethod::Shorter,
+        );
+        assert!(premultiplied.approx_eq(&straight, 1.0e-6));
+
+        // A missing alpha takes the alpha of the other color.
+        let none_alpha = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, None);
+        let mid = blue.interpolate_straight(
+            &none_alpha,
+            0.5,
+            ColorSpace::Srgb,
+            HueInterpolationMethod::Shorter,
+        );
+        assert_components!(mid, 0.0, 0.0, 1.0, 0.5);
+    }
+
+    #[test]
+    fn interpolate_eased() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+        let space = ColorSpace::Oklab;
+        let hue = HueInterpolationMethod::Shorter;
+
+        let halfway = red.interpolate(&blue, 0.5, space, hue);
+        let eased = red.interpolate_eased(&blue, 0.25, space, hue, 0.25);
+        assert!(eased.approx_eq(&halfway, 1.0e-5));
+
+        // A midpoint of 0.5 is linear.
+        for t in [0.0, 0.1, 0.7, 1.0] {
+            assert!(red
+                .interpolate_eased(&blue, t, space, hue, 0.5)
+                .approx_eq(&red.interpolate(&blue, t, space, hue), 1.0e-5));
+        }
+
+        // The ends are not moved.
+        assert!(red
+            .interpolate_eased(&blue, 0.0, space, hue, 0.9)
+            .approx_eq(&red.to_color_space(space), 1.0e-5));
+        assert!(red
+            .interpolate_eased(&blue, 1.0, space, hue, 0.1)
+            .approx_eq(&blue.to_color_space(space), 1.0e-5));
+
+        assert!(red
+            .interpolate_eased(&blue, 0.5, space, hue, 0.0)
+            .approx_eq(&blue.to_color_space(space), 1.0e-5));
+    }
+
+    #[test]
+    fn mix_uses_the_weight_of_the_first_color() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+
+        // color-mix(in srgb, red 20%, blue)
+        let mixed = red.mix(
+            &blue,
+            0.2,
+            ColorSpace::Srgb,
+            HueInterpolationMethod::Shorter,
+        );
+        assert_components!(mixed, 0.2, 0.0, 0.8, 1.0);
+    }
+
+    #[test]
+    fn mix_weighted_normalizes_weights() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+        let shorter = HueInterpolationMethod::Shorter;
+
+        // color-mix(in srgb, red, blue)
+        let mixed = red
+            .mix_weighted(None, &blue, None, ColorSpace::Srgb, shorter)
+            .unwrap();
+        assert_components!(mixed, 0.5, 0.0, 0.5, 1.0);
+
+        // color-mix(in srgb, red, blue 80%)
+        let mixed = red
+            .mix_weighted(None, &blue, Some(0.8), ColorSpace::Srgb, shorter)
+            .unwrap();
+        assert_components!(mixed, 0.2, 0.0, 0.8, 1.0);
+
+        // color-mix(in srgb, red 50%, blue 150%) is invalid.
+        assert!(red
+            .mix_weighted(Some(0.5), &blue, Some(1.5), ColorSpace::Srgb, shorter)
+            .is_none());
+
+        // color-mix(in srgb, red 0%, blue 0%) is invalid.
+        assert!(red
+            .mix_weighted(Some(0.0), &blue, Some(0.0), ColorSpace::Srgb, shorter)
+            .is_none());
+    }
+
+    #[test]
+    fn mix_weighted_scales_alpha_when_weights_sum_below_one() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+
+        // color-mix(in srgb, red 30%, blue 30%)
+        let mixed = red
+            .mix_weighted(
+                Some(0.3),
+                &blue,
+                Some(0.3),
+                ColorSpace::Srgb,
+                HueInterpolationMethod::Shorter,
+            )
+            .unwrap();
+        assert_components!(mixed, 0.5, 0.0, 0.5, 0.6);
+
+        // color-mix(in srgb, red 40%, blue 80%) scales down without touching alpha.
+        let mixed = red
+            .mix_weighted(
+                Some(0.4),
+                &blue,
+                Some(0.8),
+                ColorSpace::Srgb,
+                HueInterpolationMethod::Shorter,
+            )
+            .unwrap();
+        assert_components!(mixed, 1.0 / 3.0, 0.0, 2.0 / 3.0, 1.0);
+    }
+
+    #[test]
+    fn mix_premultiplies_alpha() {
+        // color-mix(in srgb, rgb(100% 0% 0% / 0.7) 25%, rgb(0% 100% 0% / 0.2))
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.7);
+        let green = Color::new(ColorSpace::Srgb, 0.0, 1.0, 0.0, 0.2);
+
+        let mixed = red.mix(
+            &green,
+            0.25,
+            ColorSpace::Srgb,
+            HueInterpolationMethod::Shorter,
+        );
+        assert_components!(mixed, 0.53846, 0.46154, 0.0, 0.325);
+    }
+
+    #[test]
+    fn gradient_steps() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(ColorSpace::Srgb, 0.0, 0.0, 1.0, 1.0);
+        let shorter = HueInterpolationMethod::Shorter;
+
+        let steps = red.gradient_steps(&blue, 3, ColorSpace::Oklab, shorter);
+        assert_eq!(steps.len(), 3);
+        assert!(steps[0].approx_eq(&red.to_color_space(ColorSpace::Oklab), 1.0e-6));
+        assert!(steps[2].approx_eq(&blue.to_color_space(ColorSpace::Oklab), 1.0e-6));
+        assert_eq!(
+            steps[1],
+            red.interpolate(&blue, 0.5, ColorSpace::Oklab, shorter)
+        );
+
+        assert_eq!(
+            red.gradient_steps(&blue, 1, ColorSpace::Oklab, shorter),
+            vec![red.clone()]
+        );
+        assert!(red
+            .gradient_steps(&blue, 0, ColorSpace::Oklab, shorter)
+            .is_empty());
+    }
+
+    #[test]
+    fn premultiply_round_trip() {
+        let color = Color::new(ColorSpace::Srgb, 0.8, 0.4, 0.2, 0.5);
+        let premultiplied = color.premultiply();
+        assert_components!(premultiplied, 0.4, 0.2, 0.1, 0.5);
+        assert_eq!(premultiplied.unpremultiply(), color);
+
+        let hsl = Color::new(ColorSpace::Hsl, 120.0, 0.5, 0.5, 0.5);
+        assert_components!(hsl.premultiply(), 120.0, 0.25, 0.25, 0.5);
+
+        let oklch = Color::new(ColorSpace::Oklch, 0.5, 0.2, 240.0, 0.5);
+        assert_components!(oklch.premultiply(), 0.25, 0.1, 240.0, 0.5);
+
+        let transparent = Color::new(ColorSpace::Srgb, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(transparent.unpremultiply(), transparent);
+    }
+
+    #[test]
+    fn missing_components_take_the_other_value() {
+        let from = Color::new(ColorSpace::Oklch, 0.5, None, None, 1.0);
+        let to = Color::new(ColorSpace::Oklch, 0.7, 0.1, None, 1.0);
+
+        let mid = from.interpolate(&to, 0.5, ColorSpace::Oklch, HueInterpolationMethod::Shorter);
+        assert_eq!(mid.flags, ColorFlags::C2_IS_NONE);
+        assert_components!(mid, 0.6, 0.1, 0.0, 1.0);
+    }
+}
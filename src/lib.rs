@@ -1,6 +1,52 @@
+// The tests always link `std`, for the test harness and for `format!` and
+// friends in the test modules.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![allow(clippy::excessive_precision)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the \"std\" or the \"libm\" feature must be enabled");
+
+extern crate alloc;
+
+mod adjust;
+mod angle;
+mod builder;
+#[cfg(feature = "cache")]
+mod cache;
 mod color;
+mod composite;
+mod contrast;
 mod convert;
+mod difference;
+mod gamut;
+mod interpolate;
+// With `std` linked its float methods shadow the ones from `math::Float`, so
+// the library only uses the module without `std`. The tests link `std`, and
+// compile the module with `libm` to check it against the `std` methods.
+#[cfg(any(not(any(feature = "std", test)), all(test, feature = "libm")))]
+mod math;
 mod model;
+mod named_colors;
+mod parse;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod serialize;
+#[cfg(test)]
+mod test_utils;
 
-pub use color::{Color, ColorFlags, ColorSpace, Components};
-pub use model::{Hsl, Hwb, Lab, Lch, Srgb, SrgbLinear, XyzD50, XyzD65, D50, D65};
+pub use angle::Angle;
+pub use builder::ColorBuilder;
+#[cfg(feature = "cache")]
+pub use cache::ConversionCache;
+pub use color::{Color, ColorFlags, ColorSpace, ColorSpaceMismatch, ComponentKind, Components};
+pub use composite::BlendMode;
+pub use convert::chromatic_adapt;
+pub use gamut::max_srgb_chroma_oklch;
+pub use interpolate::{adjust_hue_pair, HueInterpolationMethod};
+pub use model::{
+    tag, A98Rgb, A98RgbLinear, DisplayP3, DisplayP3Linear, Hsl, Hwb, Lab, Lch, Oklab, Oklch,
+    ProphotoRgb, ProphotoRgbLinear, Rec2020, Rec2020Linear, Rgb, Srgb, SrgbLinear, WhitePoint, Xyz,
+    XyzD50, XyzD55, XyzD65, XyzD75, D50, D55, D65, D75,
+};
+pub use named_colors::named_color;
+pub use parse::{ParseError, ParsedColor};
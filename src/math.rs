@@ -0,0 +1,139 @@
+//! Float functions that `core` does not provide, implemented with `libm` for
+//! builds without `std`. The methods have the same names as their `std`
+//! counterparts, so the code using them does not change; modules only have to
+//! import [`Float`] when `std` is not available.
+
+pub(crate) trait Float: Sized {
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn round(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($ty:ty, $powf:ident, $sqrt:ident, $cbrt:ident, $hypot:ident, $exp:ident,
+     $ln:ident, $sin:ident, $cos:ident, $atan2:ident, $round:ident, $fmod:ident) => {
+        impl Float for $ty {
+            fn powf(self, n: Self) -> Self {
+                libm::$powf(self, n)
+            }
+
+            fn powi(self, n: i32) -> Self {
+                libm::$powf(self, n as Self)
+            }
+
+            fn sqrt(self) -> Self {
+                libm::$sqrt(self)
+            }
+
+            fn cbrt(self) -> Self {
+                libm::$cbrt(self)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                libm::$hypot(self, other)
+            }
+
+            fn exp(self) -> Self {
+                libm::$exp(self)
+            }
+
+            fn ln(self) -> Self {
+                libm::$ln(self)
+            }
+
+            fn sin(self) -> Self {
+                libm::$sin(self)
+            }
+
+            fn cos(self) -> Self {
+                libm::$cos(self)
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                libm::$atan2(self, other)
+            }
+
+            fn round(self) -> Self {
+                libm::$round(self)
+            }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                let r = libm::$fmod(self, rhs);
+                if r < 0.0 {
+                    r + rhs.abs()
+                } else {
+                    r
+                }
+            }
+        }
+    };
+}
+
+impl_float!(f32, powf, sqrtf, cbrtf, hypotf, expf, logf, sinf, cosf, atan2f, roundf, fmodf);
+impl_float!(f64, pow, sqrt, cbrt, hypot, exp, log, sin, cos, atan2, round, fmod);
+
+#[cfg(test)]
+mod tests {
+    use super::Float;
+
+    /// Check the `libm` implementations against the `std` methods, which the
+    /// tests link. The trait has to be named, or the `std` methods would be
+    /// called instead.
+    macro_rules! matches_std {
+        ($name:ident, $ty:ty) => {
+            #[test]
+            fn $name() {
+                let close = |a: $ty, b: $ty| {
+                    a == b
+                        || (a.is_nan() && b.is_nan())
+                        || (a - b).abs() <= 1.0e-6 * b.abs().max(1.0)
+                };
+
+                let values: [$ty; 9] = [-400.0, -2.5, -1.0e-6, -0.0, 0.0, 0.5, 1.0, 3.75, 720.0];
+                for x in values {
+                    for (name, actual, expected) in [
+                        ("powf", Float::powf(x, 2.4), x.powf(2.4)),
+                        ("powi", Float::powi(x, 3), x.powi(3)),
+                        ("powi", Float::powi(x, -2), x.powi(-2)),
+                        ("sqrt", Float::sqrt(x), x.sqrt()),
+                        ("cbrt", Float::cbrt(x), x.cbrt()),
+                        ("hypot", Float::hypot(x, 2.0), x.hypot(2.0)),
+                        ("exp", Float::exp(x), x.exp()),
+                        ("ln", Float::ln(x), x.ln()),
+                        ("sin", Float::sin(x), x.sin()),
+                        ("cos", Float::cos(x), x.cos()),
+                        ("atan2", Float::atan2(x, -1.0), x.atan2(-1.0)),
+                        ("round", Float::round(x), x.round()),
+                        (
+                            "rem_euclid",
+                            Float::rem_euclid(x, 360.0),
+                            x.rem_euclid(360.0),
+                        ),
+                        (
+                            "rem_euclid",
+                            Float::rem_euclid(x, -360.0),
+                            x.rem_euclid(-360.0),
+                        ),
+                    ] {
+                        assert!(
+                            close(actual, expected),
+                            "{name}({x}): {actual} != {expected}"
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    matches_std!(f32_matches_std, f32);
+    matches_std!(f64_matches_std, f64);
+}
@@ -11,7 +11,7 @@ pub struct Hsl {
 }
 
 impl Hsl {
-    pub fn new(hue: f32, saturation: f32, lightness: f32, flags: ColorFlags) -> Self {
+    pub const fn new(hue: f32, saturation: f32, lightness: f32, flags: ColorFlags) -> Self {
         Self {
             hue,
             saturation,
@@ -24,6 +24,17 @@ impl Hsl {
 impl ColorSpaceModel for Hsl {
     const COLOR_SPACE: ColorSpace = ColorSpace::Hsl;
 
+    fn components(&self) -> Components {
+        Components(self.hue, self.saturation, self.lightness)
+    }
+
+    fn from_components(
+        Components(hue, saturation, lightness): Components,
+        flags: ColorFlags,
+    ) -> Self {
+        Self::new(hue, saturation, lightness, flags)
+    }
+
     fn into_color(self, alpha: f32) -> Color {
         Color {
             components: Components(self.hue, self.saturation, self.lightness),
@@ -11,7 +11,7 @@ pub struct Hwb {
 }
 
 impl Hwb {
-    pub fn new(hue: f32, whiteness: f32, blackness: f32, flags: ColorFlags) -> Self {
+    pub const fn new(hue: f32, whiteness: f32, blackness: f32, flags: ColorFlags) -> Self {
         Self {
             hue,
             whiteness,
@@ -24,6 +24,17 @@ impl Hwb {
 impl ColorSpaceModel for Hwb {
     const COLOR_SPACE: ColorSpace = ColorSpace::Hwb;
 
+    fn components(&self) -> Components {
+        Components(self.hue, self.whiteness, self.blackness)
+    }
+
+    fn from_components(
+        Components(hue, whiteness, blackness): Components,
+        flags: ColorFlags,
+    ) -> Self {
+        Self::new(hue, whiteness, blackness, flags)
+    }
+
     fn into_color(self, alpha: f32) -> Color {
         Color {
             components: Components(self.hue, self.whiteness, self.blackness),
@@ -1,4 +1,4 @@
-use super::ColorSpaceModel;
+use super::{wrap_hue, ColorSpaceModel};
 use crate::{Color, ColorFlags, ColorSpace, Components};
 
 #[repr(C)]
@@ -10,7 +10,7 @@ pub struct Lab {
 }
 
 impl Lab {
-    pub fn new(lightness: f32, a: f32, b: f32, flags: ColorFlags) -> Self {
+    pub const fn new(lightness: f32, a: f32, b: f32, flags: ColorFlags) -> Self {
         Self {
             lightness,
             a,
@@ -23,6 +23,14 @@ impl Lab {
 impl ColorSpaceModel for Lab {
     const COLOR_SPACE: ColorSpace = ColorSpace::Lab;
 
+    fn components(&self) -> Components {
+        Components(self.lightness, self.a, self.b)
+    }
+
+    fn from_components(Components(lightness, a, b): Components, flags: ColorFlags) -> Self {
+        Self::new(lightness, a, b, flags)
+    }
+
     fn into_color(self, alpha: f32) -> Color {
         Color {
             components: Components(self.lightness, self.a, self.b),
@@ -42,11 +50,13 @@ pub struct Lch {
 }
 
 impl Lch {
-    pub fn new(lightness: f32, chroma: f32, hue: f32, flags: ColorFlags) -> Self {
+    /// Create the model, with the hue wrapped into `[0, 360)` degrees. A NaN
+    /// hue, which marks the hue as powerless, is kept.
+    pub const fn new(lightness: f32, chroma: f32, hue: f32, flags: ColorFlags) -> Self {
         Self {
             lightness,
             chroma,
-            hue,
+            hue: wrap_hue(hue),
             flags,
         }
     }
@@ -55,6 +65,14 @@ impl Lch {
 impl ColorSpaceModel for Lch {
     const COLOR_SPACE: ColorSpace = ColorSpace::Lch;
 
+    fn components(&self) -> Components {
+        Components(self.lightness, self.chroma, self.hue)
+    }
+
+    fn from_components(Components(lightness, chroma, hue): Components, flags: ColorFlags) -> Self {
+        Self::new(lightness, chroma, hue, flags)
+    }
+
     fn into_color(self, alpha: f32) -> Color {
         Color {
             components: Components(self.lightness, self.chroma, self.hue),
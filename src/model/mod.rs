@@ -1,26 +1,256 @@
-use crate::{Color, ColorSpace, Components};
+use crate::{Color, ColorFlags, ColorSpace, ColorSpaceMismatch, Components};
 
 mod hsl;
 mod hwb;
 mod lab_lch;
+mod oklab_oklch;
 mod rgb;
 mod xyz;
 
 pub use hsl::Hsl;
 pub use hwb::Hwb;
 pub use lab_lch::{Lab, Lch};
-pub use rgb::{Rgb, Srgb, SrgbLinear};
-pub use xyz::{WhitePoint, XyzD50, XyzD65, D50, D65};
+pub use oklab_oklch::{Oklab, Oklch};
+pub use rgb::{
+    tag, A98Rgb, A98RgbLinear, DisplayP3, DisplayP3Linear, ProphotoRgb, ProphotoRgbLinear, Rec2020,
+    Rec2020Linear, Rgb, Srgb, SrgbLinear,
+};
+pub use xyz::{WhitePoint, Xyz, XyzD50, XyzD55, XyzD65, XyzD75, D50, D55, D65, D75};
 
-pub trait ColorSpaceModel {
+/// Wrap a hue in degrees into `[0, 360)`, like `rem_euclid`, which is not
+/// available in a `const fn`. A NaN (powerless) hue stays NaN.
+pub(crate) const fn wrap_hue(hue: f32) -> f32 {
+    let hue = hue % 360.0;
+    if hue < 0.0 {
+        let hue = hue + 360.0;
+        // Tiny negative hues round up to 360 when it is added.
+        if hue == 360.0 {
+            0.0
+        } else {
+            hue
+        }
+    } else {
+        hue
+    }
+}
+
+mod layout {
+    /// Implemented only by `impl_color_layout!`, for the models whose fields
+    /// are laid out like the start of a [`Color`](crate::Color).
+    pub trait ColorLayout {}
+}
+
+/// Check at compile time that the three components and the flags of each model
+/// are at the same offsets as in a [`Color`], so that [`Color::as_model`] can
+/// borrow a color as its model.
+macro_rules! impl_color_layout {
+    ($($model:ty { $c0:ident, $c1:ident, $c2:ident }),* $(,)?) => {
+        $(
+            impl layout::ColorLayout for $model {}
+
+            const _: () = {
+                use core::mem::{align_of, offset_of, size_of};
+
+                let components = offset_of!(Color, components);
+                assert!(offset_of!($model, $c0) == components + offset_of!(Components, 0));
+                assert!(offset_of!($model, $c1) == components + offset_of!(Components, 1));
+                assert!(offset_of!($model, $c2) == components + offset_of!(Components, 2));
+                assert!(offset_of!($model, flags) == offset_of!(Color, flags));
+                assert!(size_of::<$model>() <= size_of::<Color>());
+                assert!(align_of::<$model>() <= align_of::<Color>());
+            };
+        )*
+    };
+}
+
+impl_color_layout!(
+    Srgb { red, green, blue },
+    SrgbLinear { red, green, blue },
+    DisplayP3 { red, green, blue },
+    A98Rgb { red, green, blue },
+    ProphotoRgb { red, green, blue },
+    Rec2020 { red, green, blue },
+    Hsl {
+        hue,
+        saturation,
+        lightness
+    },
+    Hwb {
+        hue,
+        whiteness,
+        blackness
+    },
+    Lab { lightness, a, b },
+    Lch {
+        lightness,
+        chroma,
+        hue
+    },
+    Oklab { lightness, a, b },
+    Oklch {
+        lightness,
+        chroma,
+        hue
+    },
+    XyzD50 { x, y, z },
+    XyzD65 { x, y, z },
+);
+
+/// A color space's own representation of a color, with named fields. The
+/// models are only implemented by this crate, because [`Color::as_model`]
+/// relies on their layout.
+pub trait ColorSpaceModel: Sized + layout::ColorLayout {
     const COLOR_SPACE: ColorSpace;
 
-    fn components(&self) -> &Components
-    where
-        Self: Sized,
-    {
-        unsafe { std::mem::transmute(self) }
-    }
+    /// The components of the model, in the order they are stored in a
+    /// [`Color`].
+    fn components(&self) -> Components;
+
+    /// Create the model from components in the order they are stored in a
+    /// [`Color`].
+    fn from_components(components: Components, flags: ColorFlags) -> Self;
 
     fn into_color(self, alpha: f32) -> Color;
 }
+
+/// Convert a model and its alpha into a [`Color`].
+impl<C: ColorSpaceModel> From<(C, f32)> for Color {
+    fn from((model, alpha): (C, f32)) -> Self {
+        model.into_color(alpha)
+    }
+}
+
+macro_rules! impl_try_from_color {
+    ($($model:ty),*) => {
+        $(
+            impl TryFrom<Color> for $model {
+                type Error = ColorSpaceMismatch;
+
+                fn try_from(color: Color) -> Result<Self, Self::Error> {
+                    color.try_as_model::<Self>()?;
+                    Ok(Self::from_components(color.components, color.flags))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_color!(
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    A98Rgb,
+    ProphotoRgb,
+    Rec2020,
+    Hsl,
+    Hwb,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    XyzD50,
+    XyzD65
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_model<C: ColorSpaceModel>(model: C, fields: [f32; 3]) {
+        assert_eq!(model.components(), Components::from(fields));
+
+        let color = model.into_color(0.5);
+        assert_eq!(color.color_space, C::COLOR_SPACE);
+        assert_eq!(color.components, Components::from(fields));
+        assert_eq!(color.alpha, 0.5);
+
+        assert_eq!(color.as_model::<C>().components(), Components::from(fields));
+
+        let model = color.to_model::<C>().unwrap();
+        assert_eq!(model.components(), Components::from(fields));
+    }
+
+    #[test]
+    fn components_are_in_field_order() {
+        let flags = ColorFlags::empty();
+
+        assert_model(Srgb::new(0.1, 0.2, 0.3, flags), [0.1, 0.2, 0.3]);
+        assert_model(SrgbLinear::new(0.1, 0.2, 0.3, flags), [0.1, 0.2, 0.3]);
+        assert_model(DisplayP3::new(0.1, 0.2, 0.3, flags), [0.1, 0.2, 0.3]);
+        assert_model(A98Rgb::new(0.1, 0.2, 0.3, flags), [0.1, 0.2, 0.3]);
+        assert_model(ProphotoRgb::new(0.1, 0.2, 0.3, flags), [0.1, 0.2, 0.3]);
+        assert_model(Rec2020::new(0.1, 0.2, 0.3, flags), [0.1, 0.2, 0.3]);
+        assert_model(Hsl::new(120.0, 0.5, 0.25, flags), [120.0, 0.5, 0.25]);
+        assert_model(Hwb::new(120.0, 0.5, 0.25, flags), [120.0, 0.5, 0.25]);
+        assert_model(Lab::new(50.0, 20.0, -30.0, flags), [50.0, 20.0, -30.0]);
+        assert_model(Lch::new(50.0, 30.0, 270.0, flags), [50.0, 30.0, 270.0]);
+        assert_model(Oklab::new(0.5, 0.1, -0.1, flags), [0.5, 0.1, -0.1]);
+        assert_model(Oklch::new(0.5, 0.1, 270.0, flags), [0.5, 0.1, 270.0]);
+        assert_model(XyzD50::new(0.1, 0.2, 0.3, flags), [0.1, 0.2, 0.3]);
+        assert_model(XyzD65::new(0.1, 0.2, 0.3, flags), [0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn to_model_checks_the_color_space() {
+        let color = Color::new(ColorSpace::Lab, 50.0, None, -30.0, 1.0);
+
+        let lab = color.to_model::<Lab>().unwrap();
+        assert_eq!((lab.lightness, lab.a, lab.b), (50.0, 0.0, -30.0));
+        assert_eq!(lab.flags, ColorFlags::C1_IS_NONE);
+
+        assert!(color.to_model::<Lch>().is_none());
+        assert!(color.to_model::<XyzD50>().is_none());
+    }
+
+    #[test]
+    fn const_constructors() {
+        const RED: Srgb = Srgb::new(1.0, 0.0, 0.0, ColorFlags::empty());
+        const GRAY: Lch = Lch::new(50.0, 0.0, 0.0, ColorFlags::C2_IS_NONE);
+        const WHITE: XyzD65 = XyzD65::new(0.9505, 1.0, 1.089, ColorFlags::empty());
+
+        assert_eq!(RED.components(), Components(1.0, 0.0, 0.0));
+        assert_eq!(GRAY.flags, ColorFlags::C2_IS_NONE);
+        assert_eq!(WHITE.y, 1.0);
+    }
+
+    #[test]
+    fn from_and_try_from_color() {
+        let hsl = Hsl::new(0.0, 0.5, 0.25, ColorFlags::C0_IS_NONE);
+        let color: Color = (hsl, 0.75).into();
+        assert_eq!(color, Color::new(ColorSpace::Hsl, None, 0.5, 0.25, 0.75));
+
+        let hsl = Hsl::try_from(color.clone()).unwrap();
+        assert_eq!((hsl.hue, hsl.saturation, hsl.lightness), (0.0, 0.5, 0.25));
+        assert_eq!(hsl.flags, ColorFlags::C0_IS_NONE);
+
+        let err = Lab::try_from(color).err().unwrap();
+        assert_eq!(
+            err,
+            ColorSpaceMismatch {
+                expected: ColorSpace::Lab,
+                actual: ColorSpace::Hsl,
+            }
+        );
+    }
+
+    #[test]
+    fn polar_constructors_wrap_the_hue() {
+        assert_eq!(Lch::new(50.0, 30.0, -90.0, ColorFlags::empty()).hue, 270.0);
+        assert_eq!(Lch::new(50.0, 30.0, 360.0, ColorFlags::empty()).hue, 0.0);
+        assert_eq!(Oklch::new(0.5, 0.1, 450.0, ColorFlags::empty()).hue, 90.0);
+        assert_eq!(Oklch::new(0.5, 0.1, -1.0e-6, ColorFlags::empty()).hue, 0.0);
+        assert!(Lch::new(50.0, 0.0, f32::NAN, ColorFlags::empty())
+            .hue
+            .is_nan());
+
+        const LCH: Lch = Lch::new(50.0, 30.0, -30.0, ColorFlags::empty());
+        assert_eq!(LCH.hue, 330.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn as_model_panics_for_other_color_spaces() {
+        let color = Color::new(ColorSpace::Srgb, 0.1, 0.2, 0.3, 1.0);
+        let _ = color.as_model::<DisplayP3>();
+    }
+}
@@ -0,0 +1,84 @@
+use super::{wrap_hue, ColorSpaceModel};
+use crate::{Color, ColorFlags, ColorSpace, Components};
+
+#[repr(C)]
+pub struct Oklab {
+    pub lightness: f32,
+    pub a: f32,
+    pub b: f32,
+    pub flags: ColorFlags,
+}
+
+impl Oklab {
+    pub const fn new(lightness: f32, a: f32, b: f32, flags: ColorFlags) -> Self {
+        Self {
+            lightness,
+            a,
+            b,
+            flags,
+        }
+    }
+}
+
+impl ColorSpaceModel for Oklab {
+    const COLOR_SPACE: ColorSpace = ColorSpace::Oklab;
+
+    fn components(&self) -> Components {
+        Components(self.lightness, self.a, self.b)
+    }
+
+    fn from_components(Components(lightness, a, b): Components, flags: ColorFlags) -> Self {
+        Self::new(lightness, a, b, flags)
+    }
+
+    fn into_color(self, alpha: f32) -> Color {
+        Color {
+            components: Components(self.lightness, self.a, self.b),
+            flags: self.flags,
+            color_space: Self::COLOR_SPACE,
+            alpha,
+        }
+    }
+}
+
+#[repr(C)]
+pub struct Oklch {
+    pub lightness: f32,
+    pub chroma: f32,
+    pub hue: f32,
+    pub flags: ColorFlags,
+}
+
+impl Oklch {
+    /// Create the model, with the hue wrapped into `[0, 360)` degrees. A NaN
+    /// hue, which marks the hue as powerless, is kept.
+    pub const fn new(lightness: f32, chroma: f32, hue: f32, flags: ColorFlags) -> Self {
+        Self {
+            lightness,
+            chroma,
+            hue: wrap_hue(hue),
+            flags,
+        }
+    }
+}
+
+impl ColorSpaceModel for Oklch {
+    const COLOR_SPACE: ColorSpace = ColorSpace::Oklch;
+
+    fn components(&self) -> Components {
+        Components(self.lightness, self.chroma, self.hue)
+    }
+
+    fn from_components(Components(lightness, chroma, hue): Components, flags: ColorFlags) -> Self {
+        Self::new(lightness, chroma, hue, flags)
+    }
+
+    fn into_color(self, alpha: f32) -> Color {
+        Color {
+            components: Components(self.lightness, self.chroma, self.hue),
+            flags: self.flags,
+            color_space: Self::COLOR_SPACE,
+            alpha,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::{Color, ColorFlags, ColorSpace, Components};
 
@@ -43,7 +43,7 @@ pub struct Rgb<C: tag::RgbColorSpace, E: tag::RgbEncoding> {
 }
 
 impl<C: tag::RgbColorSpace, E: tag::RgbEncoding> Rgb<C, E> {
-    pub fn new(red: f32, green: f32, blue: f32, flags: ColorFlags) -> Self {
+    pub const fn new(red: f32, green: f32, blue: f32, flags: ColorFlags) -> Self {
         Self {
             red,
             green,
@@ -56,32 +56,49 @@ impl<C: tag::RgbColorSpace, E: tag::RgbEncoding> Rgb<C, E> {
     }
 }
 
-pub type Srgb = Rgb<tag::Srgb, tag::GammaEncoded>;
-
-impl ColorSpaceModel for Srgb {
-    const COLOR_SPACE: ColorSpace = ColorSpace::Srgb;
-
-    fn into_color(self, alpha: f32) -> Color {
-        Color {
-            components: Components(self.red, self.green, self.blue),
-            flags: self.flags,
-            color_space: Self::COLOR_SPACE,
-            alpha,
-        }
-    }
+macro_rules! impl_rgb_model {
+    ($($model:ty => $color_space:ident),* $(,)?) => {
+        $(
+            impl ColorSpaceModel for $model {
+                const COLOR_SPACE: ColorSpace = ColorSpace::$color_space;
+
+                fn components(&self) -> Components {
+                    Components(self.red, self.green, self.blue)
+                }
+
+                fn from_components(Components(red, green, blue): Components, flags: ColorFlags) -> Self {
+                    Self::new(red, green, blue, flags)
+                }
+
+                fn into_color(self, alpha: f32) -> Color {
+                    Color {
+                        components: Components(self.red, self.green, self.blue),
+                        flags: self.flags,
+                        color_space: Self::COLOR_SPACE,
+                        alpha,
+                    }
+                }
+            }
+        )*
+    };
 }
 
+pub type Srgb = Rgb<tag::Srgb, tag::GammaEncoded>;
 pub type SrgbLinear = Rgb<tag::Srgb, tag::LinearLight>;
-
-impl ColorSpaceModel for SrgbLinear {
-    const COLOR_SPACE: ColorSpace = ColorSpace::SrgbLinear;
-
-    fn into_color(self, alpha: f32) -> Color {
-        Color {
-            components: Components(self.red, self.green, self.blue),
-            flags: self.flags,
-            color_space: Self::COLOR_SPACE,
-            alpha,
-        }
-    }
-}
+pub type DisplayP3 = Rgb<tag::DisplayP3, tag::GammaEncoded>;
+pub type DisplayP3Linear = Rgb<tag::DisplayP3, tag::LinearLight>;
+pub type A98Rgb = Rgb<tag::A98Rgb, tag::GammaEncoded>;
+pub type A98RgbLinear = Rgb<tag::A98Rgb, tag::LinearLight>;
+pub type ProphotoRgb = Rgb<tag::ProphotoRgb, tag::GammaEncoded>;
+pub type ProphotoRgbLinear = Rgb<tag::ProphotoRgb, tag::LinearLight>;
+pub type Rec2020 = Rgb<tag::Rec2020, tag::GammaEncoded>;
+pub type Rec2020Linear = Rgb<tag::Rec2020, tag::LinearLight>;
+
+impl_rgb_model!(
+    Srgb => Srgb,
+    SrgbLinear => SrgbLinear,
+    DisplayP3 => DisplayP3,
+    A98Rgb => A98Rgb,
+    ProphotoRgb => ProphotoRgb,
+    Rec2020 => Rec2020,
+);
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use super::ColorSpaceModel;
 use crate::{Color, ColorFlags, ColorSpace, Components};
@@ -17,6 +17,18 @@ impl WhitePoint for D65 {
     const WHITE_POINT: Components = Components(0.9504559270516716, 1.0, 1.0890577507598784);
 }
 
+/// The CIE D55 white point, roughly 5500K, common in photography.
+pub struct D55;
+impl WhitePoint for D55 {
+    const WHITE_POINT: Components = Components(0.956797052643698, 1.0, 0.9214805860173273);
+}
+
+/// The CIE D75 white point, roughly 7500K, used for north sky daylight.
+pub struct D75;
+impl WhitePoint for D75 {
+    const WHITE_POINT: Components = Components(0.9497220898840717, 1.0, 1.226393520724154);
+}
+
 #[repr(C)]
 pub struct Xyz<W: WhitePoint> {
     pub x: f32,
@@ -28,7 +40,7 @@ pub struct Xyz<W: WhitePoint> {
 }
 
 impl<W: WhitePoint> Xyz<W> {
-    pub fn new(x: f32, y: f32, z: f32, flags: ColorFlags) -> Self {
+    pub const fn new(x: f32, y: f32, z: f32, flags: ColorFlags) -> Self {
         Self {
             x,
             y,
@@ -45,6 +57,14 @@ pub type XyzD50 = Xyz<D50>;
 impl ColorSpaceModel for XyzD50 {
     const COLOR_SPACE: ColorSpace = ColorSpace::XyzD50;
 
+    fn components(&self) -> Components {
+        Components(self.x, self.y, self.z)
+    }
+
+    fn from_components(Components(x, y, z): Components, flags: ColorFlags) -> Self {
+        Self::new(x, y, z, flags)
+    }
+
     fn into_color(self, alpha: f32) -> Color {
         Color {
             components: Components(self.x, self.y, self.z),
@@ -60,6 +80,14 @@ pub type XyzD65 = Xyz<D65>;
 impl ColorSpaceModel for XyzD65 {
     const COLOR_SPACE: ColorSpace = ColorSpace::XyzD65;
 
+    fn components(&self) -> Components {
+        Components(self.x, self.y, self.z)
+    }
+
+    fn from_components(Components(x, y, z): Components, flags: ColorFlags) -> Self {
+        Self::new(x, y, z, flags)
+    }
+
     fn into_color(self, alpha: f32) -> Color {
         Color {
             components: Components(self.x, self.y, self.z),
@@ -69,3 +97,13 @@ impl ColorSpaceModel for XyzD65 {
         }
     }
 }
+
+/// XYZ relative to a D55 white point. CSS has no color space for it, so it is
+/// only used with [`crate::chromatic_adapt`] and [`crate::Lab::to_xyz`], and
+/// can not be stored in a [`Color`].
+pub type XyzD55 = Xyz<D55>;
+
+/// XYZ relative to a D75 white point. CSS has no color space for it, so it is
+/// only used with [`crate::chromatic_adapt`] and [`crate::Lab::to_xyz`], and
+/// can not be stored in a [`Color`].
+pub type XyzD75 = Xyz<D75>;
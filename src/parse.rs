@@ -0,0 +1,902 @@
+use crate::{model::wrap_hue, named_colors::named_color, Angle, Color, ColorSpace};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+/// Errors that can occur while parsing a CSS color.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// The input ended before a complete color was parsed.
+    UnexpectedEnd,
+    /// A token that is not valid at its position.
+    UnexpectedToken(String),
+    /// A hex color that does not have 3, 4, 6 or 8 hexadecimal digits.
+    InvalidHex(String),
+    /// A functional notation that is not supported.
+    UnknownFunction(String),
+    /// A color space inside `color()` that is not supported.
+    UnknownColorSpace(String),
+    /// A keyword that does not name a color.
+    UnknownKeyword(String),
+    /// A function with the wrong number of components.
+    WrongComponentCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Self::UnexpectedToken(token) => write!(f, "unexpected token \"{token}\""),
+            Self::InvalidHex(hex) => write!(f, "invalid hex color \"#{hex}\""),
+            Self::UnknownFunction(name) => write!(f, "unknown color function \"{name}()\""),
+            Self::UnknownColorSpace(name) => write!(f, "unknown color space \"{name}\""),
+            Self::UnknownKeyword(name) => write!(f, "unknown color keyword \"{name}\""),
+            Self::WrongComponentCount { expected, found } => {
+                write!(f, "expected {expected} components, found {found}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+impl Color {
+    /// Parse a color from any of the CSS Color 4 notations.
+    /// <https://drafts.csswg.org/css-color-4/#color-syntax>
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut tokenizer = Tokenizer::new(input);
+
+        let color = match tokenizer.next()?.ok_or(ParseError::UnexpectedEnd)? {
+            Token::Hash(hex) => parse_hex(hex)?,
+            Token::Function(name) => parse_function(name, &mut tokenizer)?,
+            Token::Ident(name) => {
+                named_color(name).ok_or_else(|| ParseError::UnknownKeyword(name.to_string()))?
+            }
+            token => return Err(ParseError::UnexpectedToken(token.to_string())),
+        };
+
+        match tokenizer.next()? {
+            None => Ok(color),
+            Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// The result of parsing a CSS `<color>` value, which can be the
+/// `currentcolor` keyword as well as an actual color. `currentcolor` depends on
+/// the context the value is used in, so resolving it is left to the caller.
+/// [`Color::parse`] does not accept `currentcolor`.
+/// <https://drafts.csswg.org/css-color-4/#currentcolor-color>
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParsedColor {
+    /// A color that could be resolved while parsing.
+    Color(Color),
+    /// The `currentcolor` keyword.
+    CurrentColor,
+}
+
+impl ParsedColor {
+    /// Parse a CSS `<color>` value, including `currentcolor`.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut tokenizer = Tokenizer::new(input);
+
+        if let Some(Token::Ident(name)) = tokenizer.next()? {
+            if name.eq_ignore_ascii_case("currentcolor") {
+                return match tokenizer.next()? {
+                    None => Ok(Self::CurrentColor),
+                    Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+                };
+            }
+        }
+
+        Color::parse(input).map(Self::Color)
+    }
+}
+
+impl FromStr for ParsedColor {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token<'a> {
+    Number(f32),
+    /// Kept as `f64` so that dividing by 100 gives back the `f32` that was
+    /// serialized as a percentage.
+    Percentage(f64),
+    /// A number directly followed by a unit.
+    Dimension(f32, &'a str),
+    Ident(&'a str),
+    Hash(&'a str),
+    /// An identifier directly followed by an opening parenthesis.
+    Function(&'a str),
+    CloseParen,
+    Comma,
+    Slash,
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Percentage(value) => write!(f, "{value}%"),
+            Self::Dimension(value, unit) => write!(f, "{value}{unit}"),
+            Self::Ident(name) => write!(f, "{name}"),
+            Self::Hash(hex) => write!(f, "#{hex}"),
+            Self::Function(name) => write!(f, "{name}("),
+            Self::CloseParen => write!(f, ")"),
+            Self::Comma => write!(f, ","),
+            Self::Slash => write!(f, "/"),
+        }
+    }
+}
+
+/// A minimal tokenizer for the subset of CSS syntax used by color values.
+struct Tokenizer<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn consume_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let start = self.position;
+        while let Some(c) = self.peek_char() {
+            if !f(c) {
+                break;
+            }
+            self.position += c.len_utf8();
+        }
+        &self.input[start..self.position]
+    }
+
+    fn starts_number(&self) -> bool {
+        let mut chars = self.input[self.position..].chars();
+        match chars.next() {
+            Some('0'..='9') => true,
+            Some('.') => matches!(chars.next(), Some('0'..='9')),
+            Some('+' | '-') => match chars.next() {
+                Some('0'..='9') => true,
+                Some('.') => matches!(chars.next(), Some('0'..='9')),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn consume_number(&mut self) -> Result<f64, ParseError> {
+        let start = self.position;
+
+        if matches!(self.peek_char(), Some('+' | '-')) {
+            self.position += 1;
+        }
+        self.consume_while(|c| c.is_ascii_digit());
+        if self.peek_char() == Some('.') {
+            self.position += 1;
+            self.consume_while(|c| c.is_ascii_digit());
+        }
+
+        // Only treat "e" as an exponent if it is followed by digits, otherwise
+        // it is the start of a unit.
+        let rest = &self.input[self.position..];
+        let mut chars = rest.chars();
+        if matches!(chars.next(), Some('e' | 'E')) {
+            let digits = match chars.next() {
+                Some('+' | '-') => chars.next(),
+                c => c,
+            };
+            if matches!(digits, Some('0'..='9')) {
+                self.position += 1;
+                if matches!(self.peek_char(), Some('+' | '-')) {
+                    self.position += 1;
+                }
+                self.consume_while(|c| c.is_ascii_digit());
+            }
+        }
+
+        let text = &self.input[start..self.position];
+        text.parse()
+            .map_err(|_| ParseError::UnexpectedToken(text.to_string()))
+    }
+
+    fn next(&mut self) -> Result<Option<Token<'a>>, ParseError> {
+        self.consume_while(char::is_whitespace);
+
+        let Some(c) = self.peek_char() else {
+            return Ok(None);
+        };
+
+        if self.starts_number() {
+            let value = self.consume_number()?;
+            if self.peek_char() == Some('%') {
+                self.position += 1;
+                return Ok(Some(Token::Percentage(value)));
+            }
+            let unit = self.consume_while(is_name_char);
+            if !unit.is_empty() {
+                return Ok(Some(Token::Dimension(value as f32, unit)));
+            }
+            return Ok(Some(Token::Number(value as f32)));
+        }
+
+        let token = match c {
+            '#' => {
+                self.position += 1;
+                Token::Hash(self.consume_while(is_name_char))
+            }
+            '(' => return Err(ParseError::UnexpectedToken("(".to_string())),
+            ')' => {
+                self.position += 1;
+                Token::CloseParen
+            }
+            ',' => {
+                self.position += 1;
+                Token::Comma
+            }
+            '/' => {
+                self.position += 1;
+                Token::Slash
+            }
+            c if is_name_char(c) => {
+                let name = self.consume_while(is_name_char);
+                if self.peek_char() == Some('(') {
+                    self.position += 1;
+                    Token::Function(name)
+                } else {
+                    Token::Ident(name)
+                }
+            }
+            c => return Err(ParseError::UnexpectedToken(c.to_string())),
+        };
+
+        Ok(Some(token))
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Parse the digits of a hex color (excluding the leading `#`).
+/// <https://drafts.csswg.org/css-color-4/#hex-notation>
+fn parse_hex(hex: &str) -> Result<Color, ParseError> {
+    let invalid = || ParseError::InvalidHex(hex.to_string());
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).unwrap();
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+
+    let [red, green, blue, alpha] = match hex.len() {
+        3 => [digit(0) * 17, digit(1) * 17, digit(2) * 17, 255],
+        4 => [digit(0) * 17, digit(1) * 17, digit(2) * 17, digit(3) * 17],
+        6 => [pair(0), pair(2), pair(4), 255],
+        8 => [pair(0), pair(2), pair(4), pair(6)],
+        _ => return Err(invalid()),
+    };
+
+    Ok(Color::new(
+        ColorSpace::Srgb,
+        red as f32 / 255.0,
+        green as f32 / 255.0,
+        blue as f32 / 255.0,
+        alpha as f32 / 255.0,
+    ))
+}
+
+/// A single component inside a color function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Component {
+    Number(f32),
+    Percentage(f64),
+    Angle(Angle),
+    None,
+}
+
+impl Component {
+    /// Resolve a number or percentage. Angles are only allowed for hues.
+    fn map(
+        self,
+        number: impl Fn(f32) -> f32,
+        percentage: impl Fn(f64) -> f64,
+    ) -> Result<Option<f32>, ParseError> {
+        match self {
+            Self::Number(value) => Ok(Some(number(value))),
+            Self::Percentage(value) => Ok(Some(percentage(value) as f32)),
+            Self::Angle(angle) => Err(ParseError::UnexpectedToken(angle.to_string())),
+            Self::None => Ok(None),
+        }
+    }
+
+    /// Resolve a hue, given as a number of degrees or an angle, and normalize
+    /// it to `[0, 360)`.
+    fn hue(self) -> Result<Option<f32>, ParseError> {
+        let degrees = match self {
+            Self::Number(value) => value,
+            Self::Angle(angle) => angle.to_degrees(),
+            Self::Percentage(value) => {
+                return Err(ParseError::UnexpectedToken(format!("{value}%")))
+            }
+            Self::None => return Ok(None),
+        };
+        Ok(Some(wrap_hue(degrees)))
+    }
+
+    /// Resolve the component at `index` of a color in `color_space`, scaling
+    /// percentages by the reference range of the component.
+    fn resolve(self, color_space: ColorSpace, index: usize) -> Result<Option<f32>, ParseError> {
+        match percentage_reference(color_space, index) {
+            Some(reference) => self.map(|v| v, |v| v / 100.0 * f64::from(reference)),
+            None => self.hue(),
+        }
+    }
+}
+
+/// The value that 100% resolves to for the component at `index` in
+/// `color_space`, or `None` for hues, which do not accept percentages.
+///
+/// | Color space     | Lightness | Other components   |
+/// |-----------------|-----------|--------------------|
+/// | `lab`           | 100       | a and b: ±125      |
+/// | `lch`           | 100       | chroma: 150        |
+/// | `oklab`         | 1         | a and b: ±0.4      |
+/// | `oklch`         | 1         | chroma: 0.4        |
+///
+/// Every other component, including the channels of `color()`, is stored in
+/// the range `[0, 1]`, so 100% resolves to 1.
+/// <https://drafts.csswg.org/css-color-4/#specifying-lab-lch>
+/// <https://drafts.csswg.org/css-color-4/#specifying-oklab-oklch>
+fn percentage_reference(color_space: ColorSpace, index: usize) -> Option<f32> {
+    match (color_space, index) {
+        (ColorSpace::Hsl | ColorSpace::Hwb, 0) | (ColorSpace::Lch | ColorSpace::Oklch, 2) => None,
+        (ColorSpace::Lab | ColorSpace::Lch, 0) => Some(100.0),
+        (ColorSpace::Lab, _) => Some(125.0),
+        (ColorSpace::Lch, _) => Some(150.0),
+        (ColorSpace::Oklab | ColorSpace::Oklch, 0) => Some(1.0),
+        (ColorSpace::Oklab | ColorSpace::Oklch, _) => Some(0.4),
+        _ => Some(1.0),
+    }
+}
+
+/// The arguments of a color function.
+struct Arguments {
+    components: Vec<Component>,
+    alpha: Option<Component>,
+    /// True if the arguments were separated by commas.
+    legacy: bool,
+}
+
+impl Arguments {
+    fn expect_count(&self, expected: usize) -> Result<(), ParseError> {
+        if self.components.len() == expected {
+            Ok(())
+        } else {
+            Err(ParseError::WrongComponentCount {
+                expected,
+                found: self.components.len(),
+            })
+        }
+    }
+
+    /// Resolve the alpha component; a missing alpha is fully opaque.
+    fn alpha(&self) -> Result<Option<f32>, ParseError> {
+        match self.alpha {
+            Some(alpha) => alpha.map(|v| v.clamp(0.0, 1.0), |v| (v / 100.0).clamp(0.0, 1.0)),
+            None => Ok(Some(1.0)),
+        }
+    }
+}
+
+fn parse_arguments(tokenizer: &mut Tokenizer) -> Result<Arguments, ParseError> {
+    let mut tokens = vec![];
+    loop {
+        match tokenizer.next()?.ok_or(ParseError::UnexpectedEnd)? {
+            Token::CloseParen => break,
+            token => tokens.push(token),
+        }
+    }
+
+    let component = |token: Token| match token {
+        Token::Number(value) => Ok(Component::Number(value)),
+        Token::Percentage(value) => Ok(Component::Percentage(value)),
+        Token::Dimension(value, unit) => Angle::from_unit(value, unit)
+            .map(Component::Angle)
+            .ok_or_else(|| ParseError::UnexpectedToken(token.to_string())),
+        Token::Ident(name) if name.eq_ignore_ascii_case("none") => Ok(Component::None),
+        token => Err(ParseError::UnexpectedToken(token.to_string())),
+    };
+
+    // Legacy syntax separates all the components, including alpha, with commas.
+    if tokens.contains(&Token::Comma) {
+        if tokens.last() == Some(&Token::Comma) {
+            return Err(ParseError::UnexpectedToken(",".to_string()));
+        }
+
+        let mut components = vec![];
+        for (i, token) in tokens.into_iter().enumerate() {
+            match (i % 2, token) {
+                (0, token) => match component(token)? {
+                    Component::None => return Err(ParseError::UnexpectedToken("none".to_string())),
+                    component => components.push(component),
+                },
+                (_, Token::Comma) => {}
+                (_, token) => return Err(ParseError::UnexpectedToken(token.to_string())),
+            }
+        }
+        let alpha = if components.len() == 4 {
+            components.pop()
+        } else {
+            None
+        };
+        return Ok(Arguments {
+            components,
+            alpha,
+            legacy: true,
+        });
+    }
+
+    let mut tokens = tokens.into_iter();
+    let mut components = vec![];
+    let mut alpha = None;
+    while let Some(token) = tokens.next() {
+        if token == Token::Slash {
+            let token = tokens.next().ok_or(ParseError::UnexpectedEnd)?;
+            alpha = Some(component(token)?);
+            if let Some(token) = tokens.next() {
+                return Err(ParseError::UnexpectedToken(token.to_string()));
+            }
+            break;
+        }
+        components.push(component(token)?);
+    }
+
+    Ok(Arguments {
+        components,
+        alpha,
+        legacy: false,
+    })
+}
+
+fn parse_function(name: &str, tokenizer: &mut Tokenizer) -> Result<Color, ParseError> {
+    let name = name.to_ascii_lowercase();
+
+    if name == "color" {
+        return parse_color_function(tokenizer);
+    }
+
+    let arguments = parse_arguments(tokenizer)?;
+    arguments.expect_count(3)?;
+
+    let supports_legacy = matches!(name.as_str(), "rgb" | "rgba" | "hsl" | "hsla");
+    if arguments.legacy && !supports_legacy {
+        return Err(ParseError::UnexpectedToken(",".to_string()));
+    }
+
+    let [c0, c1, c2] = [0, 1, 2].map(|i| arguments.components[i]);
+    let alpha = arguments.alpha()?;
+
+    let color = match name.as_str() {
+        "rgb" | "rgba" => {
+            let channel = |c: Component| {
+                c.map(
+                    |v| (v / 255.0).clamp(0.0, 1.0),
+                    |v| (v / 100.0).clamp(0.0, 1.0),
+                )
+            };
+            Color::new(
+                ColorSpace::Srgb,
+                channel(c0)?,
+                channel(c1)?,
+                channel(c2)?,
+                alpha,
+            )
+        }
+        "hsl" | "hsla" | "hwb" => {
+            let color_space = if name == "hwb" {
+                ColorSpace::Hwb
+            } else {
+                ColorSpace::Hsl
+            };
+            let percentage = |c: Component| c.map(|v| v / 100.0, |v| v / 100.0);
+            Color::new(
+                color_space,
+                c0.hue()?,
+                percentage(c1)?,
+                percentage(c2)?,
+                alpha,
+            )
+        }
+        "lab" | "lch" | "oklab" | "oklch" => {
+            let color_space = match name.as_str() {
+                "lab" => ColorSpace::Lab,
+                "lch" => ColorSpace::Lch,
+                "oklab" => ColorSpace::Oklab,
+                _ => ColorSpace::Oklch,
+            };
+            Color::new(
+                color_space,
+                c0.resolve(color_space, 0)?,
+                c1.resolve(color_space, 1)?,
+                c2.resolve(color_space, 2)?,
+                alpha,
+            )
+        }
+        _ => return Err(ParseError::UnknownFunction(name)),
+    };
+
+    Ok(color)
+}
+
+/// Parse the arguments of the `color()` function.
+/// <https://drafts.csswg.org/css-color-4/#color-function>
+fn parse_color_function(tokenizer: &mut Tokenizer) -> Result<Color, ParseError> {
+    let color_space = match tokenizer.next()?.ok_or(ParseError::UnexpectedEnd)? {
+        // `xyz` is an alias for `xyz-d65`.
+        Token::Ident(name) if name.eq_ignore_ascii_case("xyz") => ColorSpace::XyzD65,
+        Token::Ident(name) => match ColorSpace::from_css_name(name) {
+            Some(color_space)
+                if color_space.is_rgb_like()
+                    || color_space.is_xyz_like()
+                    || color_space == ColorSpace::SrgbLinear =>
+            {
+                color_space
+            }
+            _ => return Err(ParseError::UnknownColorSpace(name.to_string())),
+        },
+        token => return Err(ParseError::UnexpectedToken(token.to_string())),
+    };
+
+    let arguments = parse_arguments(tokenizer)?;
+    if arguments.legacy {
+        return Err(ParseError::UnexpectedToken(",".to_string()));
+    }
+    arguments.expect_count(3)?;
+
+    let [c0, c1, c2] = [0, 1, 2].map(|i| arguments.components[i].resolve(color_space, i));
+
+    Ok(Color::new(color_space, c0?, c1?, c2?, arguments.alpha()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColorFlags;
+
+    fn assert_parses(input: &str, expected: Color) {
+        let color = Color::parse(input).unwrap_or_else(|err| panic!("{input}: {err}"));
+        assert_eq!(color.color_space, expected.color_space, "{input}");
+        assert_eq!(color.flags, expected.flags, "{input}");
+        for (c, e) in [
+            (color.components.0, expected.components.0),
+            (color.components.1, expected.components.1),
+            (color.components.2, expected.components.2),
+            (color.alpha, expected.alpha),
+        ] {
+            assert!((c - e).abs() < 1.0e-4, "{input}: {c} is not equal to {e}");
+        }
+    }
+
+    #[test]
+    fn hex() {
+        assert_parses("#f00", Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0));
+        assert_parses("#f008", Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5333));
+        assert_parses(
+            "#D2691E",
+            Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0),
+        );
+        assert_parses(
+            "#d2691e80",
+            Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 0.502),
+        );
+    }
+
+    #[test]
+    fn rgb() {
+        let expected = Color::new(ColorSpace::Srgb, 1.0, 0.5, 0.0, 1.0);
+        assert_parses("rgb(255 127.5 0)", expected.clone());
+        assert_parses("rgb(100% 50% 0%)", expected.clone());
+        assert_parses("rgb(255, 127.5, 0)", expected.clone());
+        assert_parses("RGB(255 127.5 0 / 1)", expected);
+
+        assert_parses(
+            "rgba(255, 0, 0, 0.5)",
+            Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5),
+        );
+        assert_parses(
+            "rgb(none 0 0 / 50%)",
+            Color::new(ColorSpace::Srgb, None, 0.0, 0.0, 0.5),
+        );
+        assert_parses(
+            "rgb(300 0 -10)",
+            Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn hsl_and_hwb() {
+        assert_parses(
+            "hsl(25 75% 47.06%)",
+            Color::new(ColorSpace::Hsl, 25.0, 0.75, 0.4706, 1.0),
+        );
+        assert_parses(
+            "hsla(25, 75%, 47.06%, 0.25)",
+            Color::new(ColorSpace::Hsl, 25.0, 0.75, 0.4706, 0.25),
+        );
+        assert_parses(
+            "hsl(none 75 47.06)",
+            Color::new(ColorSpace::Hsl, None, 0.75, 0.4706, 1.0),
+        );
+        assert_parses(
+            "hwb(25 11.76% 17.65%)",
+            Color::new(ColorSpace::Hwb, 25.0, 0.1176, 0.1765, 1.0),
+        );
+    }
+
+    #[test]
+    fn lab_lch_oklab_oklch() {
+        assert_parses(
+            "lab(56.6293 39.2371 57.5538)",
+            Color::new(ColorSpace::Lab, 56.6293, 39.2371, 57.5538, 1.0),
+        );
+        assert_parses(
+            "lch(56.6293 69.6562 55.7159 / 0.5)",
+            Color::new(ColorSpace::Lch, 56.6293, 69.6562, 55.7159, 0.5),
+        );
+        assert_parses(
+            "oklab(0.628 0.225 0.126)",
+            Color::new(ColorSpace::Oklab, 0.628, 0.225, 0.126, 1.0),
+        );
+        assert_parses(
+            "oklch(0.7 0.15 none)",
+            Color::new(ColorSpace::Oklch, 0.7, 0.15, None, 1.0),
+        );
+    }
+
+    #[test]
+    fn percentages() {
+        assert_parses(
+            "color(srgb 25% 50% 100%)",
+            Color::new(ColorSpace::Srgb, 0.25, 0.5, 1.0, 1.0),
+        );
+        assert_parses(
+            "lab(50% 25% -50%)",
+            Color::new(ColorSpace::Lab, 50.0, 31.25, -62.5, 1.0),
+        );
+        assert_parses(
+            "lch(50% 50% 120)",
+            Color::new(ColorSpace::Lch, 50.0, 75.0, 120.0, 1.0),
+        );
+        assert_parses(
+            "oklab(50% -50% 100%)",
+            Color::new(ColorSpace::Oklab, 0.5, -0.2, 0.4, 1.0),
+        );
+        assert_parses(
+            "oklch(60% 25% 30)",
+            Color::new(ColorSpace::Oklch, 0.6, 0.1, 30.0, 1.0),
+        );
+
+        assert_parses(
+            "rgb(255 0 0 / 25%)",
+            Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.25),
+        );
+        assert_parses(
+            "lab(50 0 0 / 150%)",
+            Color::new(ColorSpace::Lab, 50.0, 0.0, 0.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn hue_angles() {
+        for hue in [
+            "180",
+            "180deg",
+            "200grad",
+            "3.14159265rad",
+            "0.5turn",
+            "-180deg",
+        ] {
+            assert_parses(
+                &format!("hsl({hue} 100% 50%)"),
+                Color::new(ColorSpace::Hsl, 180.0, 1.0, 0.5, 1.0),
+            );
+            assert_parses(
+                &format!("lch(50 30 {hue})"),
+                Color::new(ColorSpace::Lch, 50.0, 30.0, 180.0, 1.0),
+            );
+        }
+
+        assert_parses(
+            "hsla(1.5TURN, 100%, 50%, 1)",
+            Color::new(ColorSpace::Hsl, 180.0, 1.0, 0.5, 1.0),
+        );
+        assert_parses(
+            "hwb(450 10% 20%)",
+            Color::new(ColorSpace::Hwb, 90.0, 0.1, 0.2, 1.0),
+        );
+        // Tiny negative hues wrap to 0, not 360.
+        assert_parses(
+            "hsl(-0.000001 50% 50%)",
+            Color::new(ColorSpace::Hsl, 0.0, 0.5, 0.5, 1.0),
+        );
+        assert_parses(
+            "oklch(0.5 0.1 -0.25turn)",
+            Color::new(ColorSpace::Oklch, 0.5, 0.1, 270.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn color_function() {
+        assert_parses(
+            "color(srgb 1 0.5 0)",
+            Color::new(ColorSpace::Srgb, 1.0, 0.5, 0.0, 1.0),
+        );
+        assert_parses(
+            "color(srgb 100% 50% none / none)",
+            Color::new(ColorSpace::Srgb, 1.0, 0.5, None, None),
+        );
+
+        for (name, color_space) in [
+            ("srgb-linear", ColorSpace::SrgbLinear),
+            ("display-p3", ColorSpace::DisplayP3),
+            ("a98-rgb", ColorSpace::A98Rgb),
+            ("prophoto-rgb", ColorSpace::ProphotoRgb),
+            ("rec2020", ColorSpace::Rec2020),
+            ("xyz", ColorSpace::XyzD65),
+            ("xyz-d50", ColorSpace::XyzD50),
+            ("XYZ-D65", ColorSpace::XyzD65),
+        ] {
+            assert_parses(
+                &format!("color({name} 0.25 50% none / 0.5)"),
+                Color::new(color_space, 0.25, 0.5, None, 0.5),
+            );
+        }
+    }
+
+    #[test]
+    fn none_sets_flags() {
+        let color = Color::parse("lab(none 20 none / none)").unwrap();
+        assert_eq!(
+            color.flags,
+            ColorFlags::C0_IS_NONE | ColorFlags::C2_IS_NONE | ColorFlags::ALPHA_IS_NONE
+        );
+    }
+
+    #[test]
+    fn slash_alpha() {
+        assert_parses(
+            "oklch(0.5 0.1 200 / 0.5)",
+            Color::new(ColorSpace::Oklch, 0.5, 0.1, 200.0, 0.5),
+        );
+        assert_parses(
+            "lab(50 0 0 / none)",
+            Color::new(ColorSpace::Lab, 50.0, 0.0, 0.0, None),
+        );
+        assert_parses(
+            "oklab(0.5 0.1 -0.1 / 25%)",
+            Color::new(ColorSpace::Oklab, 0.5, 0.1, -0.1, 0.25),
+        );
+        assert_parses(
+            "lab(50 0 0)",
+            Color::new(ColorSpace::Lab, 50.0, 0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(
+            Color::parse("lab(50 0 0 /)"),
+            Err(ParseError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Color::parse("lab(50 0 0 / 0.5 / 1)"),
+            Err(ParseError::UnexpectedToken("/".to_string()))
+        );
+    }
+
+    #[test]
+    fn named_colors() {
+        let color = Color::parse("rebeccapurple").unwrap();
+        assert_eq!(color.to_rgba8(), [0x66, 0x33, 0x99, 0xff]);
+
+        let color: Color = "Red".parse().unwrap();
+        assert_eq!(color.to_rgba8(), [0xff, 0x00, 0x00, 0xff]);
+    }
+
+    #[test]
+    fn transparent_and_currentcolor() {
+        assert_eq!(Color::parse("transparent"), Ok(Color::transparent()));
+        assert_eq!(
+            Color::parse("TRANSPARENT").unwrap().to_rgba8(),
+            [0, 0, 0, 0]
+        );
+
+        assert_eq!(
+            ParsedColor::parse("currentColor"),
+            Ok(ParsedColor::CurrentColor)
+        );
+        assert_eq!(
+            ParsedColor::parse("transparent"),
+            Ok(ParsedColor::Color(Color::transparent()))
+        );
+        assert_eq!(
+            ParsedColor::parse("currentcolor red"),
+            Err(ParseError::UnexpectedToken("red".to_string()))
+        );
+        assert_eq!(
+            Color::parse("currentcolor"),
+            Err(ParseError::UnknownKeyword("currentcolor".to_string()))
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let color: Color = "#ff0000".parse().unwrap();
+        assert_eq!(color, Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn errors() {
+        use ParseError as E;
+
+        let errors = [
+            ("", E::UnexpectedEnd),
+            (
+                "rgb(300 0 0 extra)",
+                E::UnexpectedToken("extra".to_string()),
+            ),
+            (
+                "rgb(255 0)",
+                E::WrongComponentCount {
+                    expected: 3,
+                    found: 2,
+                },
+            ),
+            ("rgb(255 0 0", E::UnexpectedEnd),
+            ("rgb(255 0 0) red", E::UnexpectedToken("red".to_string())),
+            ("rgb(255, 0, 0,)", E::UnexpectedToken(",".to_string())),
+            ("notacolor", E::UnknownKeyword("notacolor".to_string())),
+            ("rgb(255, 0 0)", E::UnexpectedToken("0".to_string())),
+            ("rgb(none, 0, 0)", E::UnexpectedToken("none".to_string())),
+            ("lab(50, 0, 0)", E::UnexpectedToken(",".to_string())),
+            ("#ff00f", E::InvalidHex("ff00f".to_string())),
+            ("#gg0000", E::InvalidHex("gg0000".to_string())),
+            ("foo(1 2 3)", E::UnknownFunction("foo".to_string())),
+            (
+                "color(unknown 1 2 3)",
+                E::UnknownColorSpace("unknown".to_string()),
+            ),
+            ("color(lab 50 0 0)", E::UnknownColorSpace("lab".to_string())),
+            ("hsl(10% 50% 50%)", E::UnexpectedToken("10%".to_string())),
+            ("lch(50 30 10%)", E::UnexpectedToken("10%".to_string())),
+            ("rgb(1px 0 0)", E::UnexpectedToken("1px".to_string())),
+            ("rgb(1deg 0 0)", E::UnexpectedToken("1deg".to_string())),
+            ("lab(50 10deg 0)", E::UnexpectedToken("10deg".to_string())),
+            ("hsl(10px 50% 50%)", E::UnexpectedToken("10px".to_string())),
+        ];
+
+        for (input, expected) in errors {
+            assert_eq!(Color::parse(input), Err(expected), "{input}");
+        }
+    }
+}
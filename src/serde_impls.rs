@@ -0,0 +1,147 @@
+//! `Serialize` and `Deserialize` implementations, enabled with the `serde`
+//! feature. Colors are stored as their CSS serialization so that the format
+//! stays human readable, with every number written in full. Deserializing
+//! goes through [`Color::parse`], so a round trip gives back any color the
+//! parser can produce, and normalizes other colors the way the parser does:
+//! hues are wrapped into `[0, 360)`, alpha is clamped to `[0, 1]` and a NaN
+//! component comes back as missing.
+
+use crate::{Color, ColorSpace};
+use alloc::{format, string::String};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_css_lossless())
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let css = String::deserialize(deserializer)?;
+        Color::parse(&css).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ColorSpace {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.css_name())
+    }
+}
+
+impl<'de> Deserialize<'de> for ColorSpace {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        ColorSpace::from_css_name(&name)
+            .ok_or_else(|| de::Error::custom(format!("unknown color space \"{name}\"")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, ColorFlags, ColorSpace};
+
+    fn round_trip(color: Color, json: &str) {
+        assert_eq!(serde_json::to_string(&color).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Color>(json).unwrap(), color);
+    }
+
+    #[test]
+    fn color_round_trip() {
+        round_trip(
+            Color::new(ColorSpace::Srgb, 1.0, 0.0, 51.0 / 255.0, 0.5),
+            r#""rgba(255 0 51 / 0.5)""#,
+        );
+        round_trip(
+            Color::new(ColorSpace::Lab, 56.25, 39.5, -57.0, 1.0),
+            r#""lab(56.25 39.5 -57)""#,
+        );
+        round_trip(
+            Color::new(ColorSpace::Oklch, 0.5, None, 120.0, 1.0),
+            r#""oklch(0.5 none 120)""#,
+        );
+    }
+
+    #[test]
+    fn color_round_trip_is_lossless() {
+        let colors = [
+            Color::new(ColorSpace::Srgb, 0.5, 1.2, -0.1, 1.0),
+            Color::new(ColorSpace::Srgb, 0.123_456_79, None, 0.2, 0.333_333_34),
+            Color::new(ColorSpace::Hsl, 120.5, 0.333_333_34, 0.123_456_79, 1.0),
+            Color::new(ColorSpace::Hwb, 0.1, 0.7, -0.0, 1.0),
+            Color::new(ColorSpace::Oklch, 0.627_955_4, 0.257_630_9, 29.233_885, 0.5),
+        ];
+
+        assert_eq!(
+            serde_json::to_string(&colors[0]).unwrap(),
+            r#""color(srgb 0.5 1.2 -0.1)""#
+        );
+        for color in colors {
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Color>(&json).unwrap(),
+                color,
+                "{json}"
+            );
+        }
+
+        // Percentages have to survive being scaled by 100 and back.
+        for bits in (0..1.0f32.to_bits()).step_by(100_003) {
+            let fraction = f32::from_bits(bits);
+            let color = Color::new(ColorSpace::Hsl, 0.0, fraction, fraction, 1.0);
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Color>(&json).unwrap(),
+                color,
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn color_round_trip_normalizes_like_the_parser() {
+        let colors = [
+            (
+                Color::new(ColorSpace::Hsl, 400.0, 0.5, 0.5, 1.0),
+                Color::new(ColorSpace::Hsl, 40.0, 0.5, 0.5, 1.0),
+            ),
+            (
+                Color::new(ColorSpace::Lab, 50.0, 10.0, 20.0, 1.5),
+                Color::new(ColorSpace::Lab, 50.0, 10.0, 20.0, 1.0),
+            ),
+            (
+                Color::new(ColorSpace::Lch, 50.0, 30.0, f32::NAN, 1.0),
+                Color::new(ColorSpace::Lch, 50.0, 30.0, None, 1.0),
+            ),
+        ];
+
+        for (color, expected) in colors {
+            let json = serde_json::to_string(&color).unwrap();
+            let parsed = serde_json::from_str::<Color>(&json).unwrap();
+            assert_eq!(parsed, expected, "{json}");
+        }
+    }
+
+    #[test]
+    fn invalid_color_is_an_error() {
+        assert!(serde_json::from_str::<Color>(r#""rgb(1 2)""#).is_err());
+    }
+
+    #[test]
+    fn color_space_uses_css_names() {
+        let json = serde_json::to_string(&ColorSpace::DisplayP3).unwrap();
+        assert_eq!(json, r#""display-p3""#);
+        assert_eq!(
+            serde_json::from_str::<ColorSpace>(&json).unwrap(),
+            ColorSpace::DisplayP3
+        );
+    }
+
+    #[test]
+    fn color_flags_are_not_raw_bits() {
+        let flags = ColorFlags::C0_IS_NONE | ColorFlags::ALPHA_IS_NONE;
+        let json = serde_json::to_string(&flags).unwrap();
+        assert_eq!(json, r#""C0_IS_NONE | ALPHA_IS_NONE""#);
+        assert_eq!(serde_json::from_str::<ColorFlags>(&json).unwrap(), flags);
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{Color, ColorFlags, ColorSpace};
+use alloc::{format, string::String};
+use core::fmt;
+
+/// The number of decimal places used when formatting a color with
+/// [`fmt::Display`]. This is enough to round trip through the parser without
+/// printing the noise of `f32` conversions.
+const DEFAULT_PRECISION: usize = 5;
+
+/// A single serialized number, or `none` if the component is missing.
+struct Number {
+    value: f32,
+    is_none: bool,
+    /// The number of decimal places to round to, with trailing zeros trimmed,
+    /// or `None` for the shortest number that parses back to the same `f32`.
+    precision: Option<usize>,
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_none || self.value.is_nan() {
+            return write!(f, "none");
+        }
+
+        let rounded = match self.precision {
+            Some(precision) => format!("{:.*}", precision, self.value),
+            None => format!("{}", self.value),
+        };
+        let trimmed = if rounded.contains('.') {
+            rounded.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            &rounded
+        };
+
+        // Avoid serializing "-0".
+        if trimmed == "-0" {
+            write!(f, "0")
+        } else {
+            write!(f, "{trimmed}")
+        }
+    }
+}
+
+/// A fraction serialized as a percentage.
+struct Percentage(Number);
+
+impl fmt::Display for Percentage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_none || self.0.value.is_nan() {
+            write!(f, "none")
+        } else if self.0.precision.is_none() {
+            // Scaling in `f64` is exact, so the parser can scale it back to
+            // the same `f32`. Adding 0 turns -0 into 0.
+            write!(f, "{}%", f64::from(self.0.value) * 100.0 + 0.0)
+        } else {
+            let number = Number {
+                value: self.0.value * 100.0,
+                ..self.0
+            };
+            write!(f, "{number}%")
+        }
+    }
+}
+
+impl Color {
+    fn number(&self, index: usize, precision: Option<usize>) -> Number {
+        let (value, flag) = match index {
+            0 => (self.components.0, ColorFlags::C0_IS_NONE),
+            1 => (self.components.1, ColorFlags::C1_IS_NONE),
+            2 => (self.components.2, ColorFlags::C2_IS_NONE),
+            _ => (self.alpha, ColorFlags::ALPHA_IS_NONE),
+        };
+
+        Number {
+            value,
+            is_none: self.flags.contains(flag),
+            precision,
+        }
+    }
+
+    fn has_alpha(&self) -> bool {
+        self.alpha != 1.0 || self.flags.contains(ColorFlags::ALPHA_IS_NONE)
+    }
+
+    /// Whether every present channel is in `[0, 1]` and a whole number when
+    /// scaled to `[0, 255]`, so `rgb()` can represent the color without
+    /// clamping or rounding it. Without a `precision` the channels have to be
+    /// exactly what the parser makes of the whole number.
+    fn is_8_bit(&self, precision: Option<usize>) -> bool {
+        [0, 1, 2].into_iter().all(|i| {
+            let number = self.number(i, precision);
+            let value = number.value * 255.0;
+            let whole = match precision {
+                Some(_) => (value - value.round()).abs() < 1.0e-3,
+                None => value.round() / 255.0 == number.value,
+            };
+            number.is_none || number.value.is_nan() || ((0.0..=255.0).contains(&value) && whole)
+        })
+    }
+
+    /// Serialize an sRGB color in hex notation, `#rrggbb`, or `#rrggbbaa` if
+    /// the color is not opaque. Missing components are treated as 0. Returns
+    /// `None` if the color is not in the sRGB color space.
+    pub fn to_hex(&self) -> Option<String> {
+        if self.color_space != ColorSpace::Srgb {
+            return None;
+        }
+
+        let [red, green, blue] = self.resolved_components();
+        let [red, green, blue, alpha] = [red, green, blue, self.resolved_alpha()]
+            .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8);
+
+        Some(if alpha == 255 {
+            format!("#{red:02x}{green:02x}{blue:02x}")
+        } else {
+            format!("#{red:02x}{green:02x}{blue:02x}{alpha:02x}")
+        })
+    }
+
+    /// Serialize the color like [`fmt::Display`] does, but with each number
+    /// rounded to `digits` decimal places, with trailing zeros trimmed.
+    pub fn to_css_with_precision(&self, digits: usize) -> String {
+        let mut css = String::new();
+        // Writing to a `String` can not fail.
+        let _ = self.write_css(&mut css, Some(digits));
+        css
+    }
+
+    /// Serialize the color with every number written in full, so that parsing
+    /// the result gives back the same color if the parser could have produced
+    /// it. Otherwise parsing normalizes it: hues outside `[0, 360)` are
+    /// wrapped, alpha outside `[0, 1]` is clamped and a NaN component is
+    /// written as `none`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn to_css_lossless(&self) -> String {
+        let mut css = String::new();
+        // Writing to a `String` can not fail.
+        let _ = self.write_css(&mut css, None);
+        css
+    }
+
+    /// Serialize the color according to the CSS Color 4 rules.
+    /// <https://drafts.csswg.org/css-color-4/#serializing-color-values>
+    ///
+    /// sRGB colors only use `rgb()` if the channels fit in 8 bits, otherwise
+    /// they use `color(srgb ...)` so that they are not clamped or rounded.
+    fn write_css(&self, f: &mut impl fmt::Write, precision: Option<usize>) -> fmt::Result {
+        let [c0, c1, c2] = [0, 1, 2].map(|i| self.number(i, precision));
+
+        match self.color_space {
+            ColorSpace::Srgb if self.is_8_bit(precision) => {
+                let [red, green, blue] = [0, 1, 2].map(|i| {
+                    let number = self.number(i, precision);
+                    Number {
+                        value: (number.value * 255.0).round(),
+                        ..number
+                    }
+                });
+                if self.has_alpha() {
+                    write!(f, "rgba({red} {green} {blue}")?;
+                } else {
+                    write!(f, "rgb({red} {green} {blue}")?;
+                }
+            }
+            ColorSpace::Hsl => write!(f, "hsl({c0} {} {}", Percentage(c1), Percentage(c2))?,
+            ColorSpace::Hwb => write!(f, "hwb({c0} {} {}", Percentage(c1), Percentage(c2))?,
+            ColorSpace::Lab => write!(f, "lab({c0} {c1} {c2}")?,
+            ColorSpace::Lch => write!(f, "lch({c0} {c1} {c2}")?,
+            ColorSpace::Oklab => write!(f, "oklab({c0} {c1} {c2}")?,
+            ColorSpace::Oklch => write!(f, "oklch({c0} {c1} {c2}")?,
+            color_space => write!(f, "color({} {c0} {c1} {c2}", color_space.css_name())?,
+        }
+
+        if self.has_alpha() {
+            write!(f, " / {}", self.number(3, precision))?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_css(f, Some(DEFAULT_PRECISION))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        let colors = [
+            (
+                Color::new(ColorSpace::Srgb, 1.0, 128.0 / 255.0, 0.0, 1.0),
+                "rgb(255 128 0)",
+            ),
+            (
+                Color::new(ColorSpace::Srgb, 1.0, 0.5, 0.0, 1.0),
+                "color(srgb 1 0.5 0)",
+            ),
+            (
+                Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5),
+                "rgba(255 0 0 / 0.5)",
+            ),
+            (
+                Color::new(ColorSpace::Srgb, 1.2, -0.1, None, 1.0),
+                "color(srgb 1.2 -0.1 none)",
+            ),
+            (
+                Color::new(ColorSpace::Srgb, 1.0, None, 0.2, 0.5),
+                "rgba(255 none 51 / 0.5)",
+            ),
+            (
+                Color::new(ColorSpace::Hsl, 25.0, 0.75, 0.5, 1.0),
+                "hsl(25 75% 50%)",
+            ),
+            (
+                Color::new(ColorSpace::Hwb, None, 0.25, 0.5, 0.25),
+                "hwb(none 25% 50% / 0.25)",
+            ),
+            (
+                Color::new(ColorSpace::Lab, 56.25, 39.5, -57.0, 1.0),
+                "lab(56.25 39.5 -57)",
+            ),
+            (
+                Color::new(ColorSpace::Lch, 56.25, 69.5, f32::NAN, 1.0),
+                "lch(56.25 69.5 none)",
+            ),
+            (
+                Color::new(ColorSpace::Oklab, 0.5, -0.0, 0.125, 1.0),
+                "oklab(0.5 0 0.125)",
+            ),
+            (
+                Color::new(ColorSpace::Oklch, 0.5, 0.25, 180.0, None),
+                "oklch(0.5 0.25 180 / none)",
+            ),
+            (
+                Color::new(ColorSpace::DisplayP3, 1.0, 0.0, 0.0, 1.0),
+                "color(display-p3 1 0 0)",
+            ),
+            (
+                Color::new(ColorSpace::XyzD50, 0.25, 0.5, 0.75, 0.5),
+                "color(xyz-d50 0.25 0.5 0.75 / 0.5)",
+            ),
+        ];
+
+        for (color, expected) in colors {
+            assert_eq!(color.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn alpha_is_omitted_when_opaque() {
+        let opaque = Color::new(ColorSpace::Lab, 50.0, 0.0, 0.0, 1.0);
+        assert_eq!(opaque.to_string(), "lab(50 0 0)");
+
+        let translucent = Color::new(ColorSpace::Lab, 50.0, 0.0, 0.0, 0.75);
+        assert_eq!(translucent.to_string(), "lab(50 0 0 / 0.75)");
+    }
+
+    #[test]
+    fn none_round_trips_through_the_parser() {
+        for input in [
+            "rgb(255 none 0)",
+            "hsl(none 50% 25%)",
+            "lab(none 20 30 / none)",
+            "oklch(0.5 none 120 / 0.5)",
+        ] {
+            let color = Color::parse(input).unwrap();
+            assert_eq!(color.to_string(), input);
+            assert_eq!(Color::parse(&color.to_string()).unwrap(), color);
+        }
+    }
+
+    #[test]
+    fn precision() {
+        let lab = Color::new(ColorSpace::Lab, 56.6293, 39.2371, 57.5538, 1.0);
+        assert_eq!(lab.to_css_with_precision(2), "lab(56.63 39.24 57.55)");
+        assert_eq!(lab.to_css_with_precision(0), "lab(57 39 58)");
+
+        let hsl = Color::new(ColorSpace::Hsl, 120.0, 0.1, 0.333333, 0.4);
+        assert_eq!(hsl.to_css_with_precision(1), "hsl(120 10% 33.3% / 0.4)");
+
+        // The default rounds away the noise of `f32` conversions.
+        let lab = Color::new(ColorSpace::Srgb, 0.8235, 0.4118, 0.1176, 1.0)
+            .to_color_space(ColorSpace::Lab);
+        let css = lab.to_string();
+        assert!(css.starts_with("lab("));
+        assert!(css.split(' ').all(|number| match number.split_once('.') {
+            Some((_, decimals)) => decimals.trim_end_matches(')').len() <= 5,
+            None => true,
+        }));
+
+        let tiny = Color::new(ColorSpace::Oklab, 0.5, -0.000001, 0.0, 1.0);
+        assert_eq!(tiny.to_string(), "oklab(0.5 0 0)");
+    }
+
+    #[test]
+    fn to_hex() {
+        let red = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 1.0);
+        assert_eq!(red.to_hex().as_deref(), Some("#ff0000"));
+
+        let translucent = Color::new(ColorSpace::Srgb, 1.0, 0.0, 0.0, 0.5);
+        assert_eq!(translucent.to_hex().as_deref(), Some("#ff000080"));
+
+        let missing = Color::new(ColorSpace::Srgb, 0.8235, None, 1.5, 1.0);
+        assert_eq!(missing.to_hex().as_deref(), Some("#d200ff"));
+
+        let lab = Color::new(ColorSpace::Lab, 50.0, 0.0, 0.0, 1.0);
+        assert_eq!(lab.to_hex(), None);
+    }
+}
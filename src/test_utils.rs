@@ -0,0 +1,90 @@
+//! Helpers shared by the unit tests of the different modules.
+
+use crate::{Color, ColorFlags};
+
+/// Assert that two colors are in the same color space and that each component,
+/// and the alpha, differ by no more than `epsilon`. Values that are missing or
+/// NaN in both colors are considered equal. On failure, the message lists the
+/// values of both colors side by side, with the ones that differ marked.
+#[track_caller]
+pub(crate) fn assert_color_close(a: &Color, b: &Color, epsilon: f32) {
+    if a.approx_eq(b, epsilon) {
+        return;
+    }
+
+    let values = |color: &Color| {
+        [
+            (color.components.0, ColorFlags::C0_IS_NONE),
+            (color.components.1, ColorFlags::C1_IS_NONE),
+            (color.components.2, ColorFlags::C2_IS_NONE),
+            (color.alpha, ColorFlags::ALPHA_IS_NONE),
+        ]
+        .map(|(value, flag)| (!color.flags.contains(flag)).then_some(value))
+    };
+
+    let marker = if a.color_space == b.color_space {
+        ""
+    } else {
+        "  <--"
+    };
+    let mut diff = format!(
+        "  space: {:>12} vs {:<12}{marker}\n",
+        format!("{:?}", a.color_space),
+        format!("{:?}", b.color_space)
+    );
+
+    for (name, (left, right)) in ["c0", "c1", "c2", "alpha"]
+        .into_iter()
+        .zip(values(a).into_iter().zip(values(b)))
+    {
+        let close = match (left, right) {
+            (None, None) => true,
+            (Some(l), Some(r)) => (l.is_nan() && r.is_nan()) || (l - r).abs() <= epsilon,
+            _ => false,
+        };
+        let show = |value: Option<f32>| value.map_or("none".to_string(), |v| v.to_string());
+        diff.push_str(&format!(
+            "  {name:>5}: {:>12} vs {:<12}{}\n",
+            show(left),
+            show(right),
+            if close { "" } else { "  <--" }
+        ));
+    }
+
+    panic!("colors differ by more than {epsilon}:\n{diff}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColorSpace;
+
+    #[test]
+    fn passes_for_close_colors() {
+        let color = Color::new(ColorSpace::Oklch, 0.5, 0.0, f32::NAN, None);
+        assert_color_close(&color, &color, 0.0);
+
+        let nudged = Color::new(ColorSpace::Oklch, 0.50005, 0.0, f32::NAN, None);
+        assert_color_close(&color, &nudged, 1.0e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "colors differ by more than 0.0001")]
+    fn fails_for_divergent_components() {
+        assert_color_close(
+            &Color::new(ColorSpace::Srgb, 1.0, 0.5, 0.0, 1.0),
+            &Color::new(ColorSpace::Srgb, 1.0, 0.6, 0.0, 1.0),
+            1.0e-4,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "colors differ")]
+    fn fails_for_different_color_spaces() {
+        assert_color_close(
+            &Color::new(ColorSpace::Srgb, 1.0, 0.5, 0.0, 1.0),
+            &Color::new(ColorSpace::SrgbLinear, 1.0, 0.5, 0.0, 1.0),
+            1.0e-4,
+        );
+    }
+}